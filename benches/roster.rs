@@ -0,0 +1,67 @@
+//! Times parsing a large group roster page with each JSON backend.
+//!
+//! Run with `cargo bench --features simd-json`.
+
+use roblox::GroupMember;
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+
+const MEMBERS: usize = 50_000;
+const ROUNDS: u32 = 20;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Page {
+    data: Vec<GroupMember>,
+}
+
+fn roster() -> String {
+    let data = (0..MEMBERS)
+        .map(|id| {
+            format!(
+                r#"{{"hasVerifiedBadge":false,"userId":{},"username":"member_{}","displayName":"Member {}"}}"#,
+                id, id, id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"{{"previousPageCursor":null,"nextPageCursor":"abc","data":[{}]}}"#,
+        data
+    )
+}
+
+fn time(name: &str, body: &str, parse: impl Fn(String) -> Page) {
+    let mut best = Duration::MAX;
+    for _ in 0..ROUNDS {
+        let body = body.to_string();
+        let started = Instant::now();
+        let page = parse(body);
+        best = best.min(started.elapsed());
+        assert_eq!(page.data.len(), MEMBERS);
+    }
+    println!("{:<24} {:>8.2?}", name, best);
+}
+
+fn main() {
+    let body = roster();
+    println!(
+        "{} members, {} KiB, best of {} rounds",
+        MEMBERS,
+        body.len() / 1024,
+        ROUNDS
+    );
+
+    time("serde_json via Value", &body, |body| {
+        let value: serde_json::Value = serde_json::from_str(&body).unwrap();
+        serde_json::from_value(value).unwrap()
+    });
+    time("serde_json", &body, |body| {
+        serde_json::from_str(&body).unwrap()
+    });
+    time("simd-json", &body, |body| {
+        let mut body = body.into_bytes();
+        simd_json::serde::from_slice(&mut body).unwrap()
+    });
+}
@@ -0,0 +1,194 @@
+//! A synchronous wrapper around [`crate::Client`].
+//!
+//! Like `reqwest::blocking`, every call drives the async client to completion
+//! on a runtime owned by the wrapper, so the two APIs share all of their URL
+//! building and parsing. Calling these methods from inside an async runtime
+//! will panic.
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    Api, AssetDetails, AssetType, AuditEntry, Avatar, Badge, BundleInfo, CatalogItem, CatalogQuery,
+    DeveloperProduct, Friend, GamePass, GameServer, GroupInfo, GroupMember, GroupRole,
+    GroupSearchResult, InventoryItem, JoinRequest, JoinScript, JoinStatus, MessageTab, Outfit,
+    OutfitDetails, Presence, PrivateMessage, RelationshipKind, ResaleData, Result, RobloxAssetType,
+    RobloxBadge, ServerType, Shout, ThumbnailSize, Timestamp, TradeDetail, TradeOfferInput,
+    TradeStatus, TradeSummary, UniverseInfo, UserGroupRole, UserInfo, UserSearchResult,
+    UsernameValidation, Votes, WallPost,
+};
+
+macro_rules! blocking {
+    ($($(#[$attr:meta])* fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                self.runtime.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+    runtime: Arc<Runtime>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        crate::Client::default().into()
+    }
+}
+
+impl From<crate::Client> for Client {
+    fn from(inner: crate::Client) -> Self {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("the blocking client's runtime should always build");
+
+        Client {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from the async builder; convert the built client with `into()`.
+    pub fn builder() -> crate::ClientBuilder {
+        crate::ClientBuilder::new()
+    }
+
+    pub fn invalidate_role_cache(&self, group_id: i64) {
+        self.inner.invalidate_role_cache(group_id)
+    }
+
+    pub fn base_url(&self, api: Api) -> &str {
+        self.inner.base_url(api)
+    }
+
+    pub fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        self.runtime.block_on(self.inner.get_json(url))
+    }
+
+    pub fn post_json<T: DeserializeOwned>(&self, url: &str, body: &Value) -> Result<T> {
+        self.runtime.block_on(self.inner.post_json(url, body))
+    }
+
+    blocking! {
+        fn get_user_roles(&self, roblox_id: i64) -> Result<HashMap<i64, i64>>;
+        fn get_user_group_roles(&self, user_id: i64) -> Result<Vec<UserGroupRole>>;
+        fn get_user_rank_in_group(&self, user_id: i64, group_id: i64) -> Result<Option<i64>>;
+        fn get_group_count(&self, user_id: i64) -> Result<i64>;
+        fn is_in_group(&self, user_id: i64, group_id: i64) -> Result<bool>;
+        fn is_ranked_at_least(&self, user_id: i64, group_id: i64, min_rank: i64) -> Result<bool>;
+        fn get_ranks_for_users(&self, user_ids: &[i64], group_id: i64) -> Result<HashMap<i64, Option<i64>>>;
+        fn get_user_info(&self, roblox_id: i64) -> Result<UserInfo>;
+        #[cfg(feature = "chrono")]
+        fn get_account_created(&self, user_id: i64) -> Result<DateTime<Utc>>;
+        #[cfg(feature = "chrono")]
+        fn account_age_days(&self, user_id: i64) -> Result<i64>;
+        fn get_username_from_id(&self, roblox_id: i64) -> Result<String>;
+        fn get_id_from_username(&self, username: &str) -> Result<Option<i64>>;
+        fn validate_username(&self, username: &str, birthday: &str) -> Result<UsernameValidation>;
+        fn get_ids_from_usernames(&self, usernames: &[&str], exclude_banned_users: Option<bool>) -> Result<HashMap<String, i64>>;
+        fn get_users(&self, user_ids: &[i64], exclude_banned_users: Option<bool>) -> Result<HashMap<i64, UserInfo>>;
+        fn get_username_history(&self, user_id: i64) -> Result<Vec<String>>;
+        fn search_users(&self, keyword: &str, limit: u32) -> Result<Vec<UserSearchResult>>;
+        fn get_presences(&self, user_ids: &[i64]) -> Result<HashMap<i64, Presence>>;
+        fn get_friends(&self, user_id: i64) -> Result<Vec<Friend>>;
+        fn are_friends(&self, user_id: i64, other_user_id: i64) -> Result<bool>;
+        fn get_friend_count(&self, user_id: i64) -> Result<i64>;
+        fn get_follower_count(&self, user_id: i64) -> Result<i64>;
+        fn get_following_count(&self, user_id: i64) -> Result<i64>;
+        fn send_friend_request(&self, user_id: i64) -> Result<()>;
+        fn accept_friend_request(&self, user_id: i64) -> Result<()>;
+        fn decline_friend_request(&self, user_id: i64) -> Result<()>;
+        fn get_friend_requests(&self) -> Result<Vec<UserInfo>>;
+        fn block_user(&self, user_id: i64) -> Result<()>;
+        fn unblock_user(&self, user_id: i64) -> Result<()>;
+        fn get_blocked_users(&self) -> Result<Vec<i64>>;
+        fn get_messages(&self, tab: MessageTab) -> Result<Vec<PrivateMessage>>;
+        fn send_message(&self, recipient_id: i64, subject: &str, body: &str) -> Result<()>;
+        fn get_player_badges(&self, user_id: i64) -> Result<Vec<Badge>>;
+        fn has_badge(&self, user_id: i64, badge_id: i64) -> Result<bool>;
+        fn get_badge_award_dates(&self, user_id: i64, badge_ids: &[i64]) -> Result<HashMap<i64, Timestamp>>;
+        fn get_roblox_badges(&self, user_id: i64) -> Result<Vec<RobloxBadge>>;
+        fn get_avatar(&self, user_id: i64) -> Result<Avatar>;
+        fn get_outfits(&self, user_id: i64) -> Result<Vec<Outfit>>;
+        fn get_outfit_details(&self, outfit_id: i64) -> Result<OutfitDetails>;
+        fn get_avatar_headshots(&self, user_ids: &[i64], size: ThumbnailSize) -> Result<HashMap<i64, Option<String>>>;
+        fn get_avatar_thumbnails(&self, user_ids: &[i64], size: ThumbnailSize) -> Result<HashMap<i64, Option<String>>>;
+        fn get_outfit_thumbnails(&self, outfit_ids: &[i64], size: ThumbnailSize) -> Result<HashMap<i64, Option<String>>>;
+        fn get_asset_thumbnail_3d(&self, asset_id: i64) -> Result<Option<String>>;
+        fn get_group_icons(&self, group_ids: &[i64], size: ThumbnailSize) -> Result<HashMap<i64, Option<String>>>;
+        fn has_asset(&self, roblox_id: i64, item: i64, asset_type: AssetType) -> Result<bool>;
+        fn get_inventory(&self, user_id: i64, asset_type: RobloxAssetType) -> Result<Vec<InventoryItem>>;
+        fn owns_gamepass(&self, user_id: i64, gamepass_id: i64) -> Result<bool>;
+        fn get_asset_details(&self, asset_id: i64) -> Result<AssetDetails>;
+        fn get_resale_data(&self, asset_id: i64) -> Result<ResaleData>;
+        fn search_catalog(&self, query: CatalogQuery) -> Result<Vec<CatalogItem>>;
+        fn get_bundle(&self, bundle_id: i64) -> Result<BundleInfo>;
+        fn owns_bundle(&self, user_id: i64, bundle_id: i64) -> Result<bool>;
+        fn has_premium(&self, user_id: i64) -> Result<bool>;
+        fn check_code(&self, roblox_id: i64, code: &str) -> Result<bool>;
+        fn poll_for_code(&self, user_id: i64, code: &str, interval: Duration, max_attempts: u32) -> Result<bool>;
+        fn get_group_rank(&self, group_id: i64, rank_id: i64) -> Result<Option<GroupRole>>;
+        fn get_group_ranks(&self, group_id: i64, min_rank: i64, max_rank: i64) -> Result<Vec<GroupRole>>;
+        fn get_role_member_counts(&self, group_id: i64) -> Result<Vec<(GroupRole, i64)>>;
+        fn get_role_by_name(&self, group_id: i64, name: &str) -> Result<Option<GroupRole>>;
+        fn get_role_by_rank(&self, group_id: i64, rank: i64) -> Result<Option<GroupRole>>;
+        fn get_role_members(&self, group_id: i64, role_id: i64) -> Result<Vec<GroupMember>>;
+        fn get_group(&self, group_id: i64) -> Result<GroupInfo>;
+        fn search_groups(&self, keyword: &str, limit: Option<usize>) -> Result<Vec<GroupSearchResult>>;
+        fn get_group_relationships(&self, group_id: i64, kind: RelationshipKind) -> Result<Vec<GroupInfo>>;
+        fn get_primary_group(&self, user_id: i64) -> Result<Option<GroupInfo>>;
+        fn get_group_shout(&self, group_id: i64) -> Result<Option<Shout>>;
+        fn post_shout(&self, group_id: i64, message: &str) -> Result<()>;
+        fn get_wall_posts(&self, group_id: i64) -> Result<Vec<WallPost>>;
+        fn post_to_wall(&self, group_id: i64, message: &str) -> Result<WallPost>;
+        fn delete_wall_post(&self, group_id: i64, post_id: i64) -> Result<()>;
+        fn set_rank(&self, group_id: i64, user_id: i64, role_id: i64) -> Result<()>;
+        fn promote(&self, group_id: i64, user_id: i64) -> Result<GroupRole>;
+        fn demote(&self, group_id: i64, user_id: i64) -> Result<GroupRole>;
+        fn get_audit_log(&self, group_id: i64) -> Result<Vec<AuditEntry>>;
+        fn get_join_requests(&self, group_id: i64) -> Result<Vec<JoinRequest>>;
+        fn accept_join_request(&self, group_id: i64, user_id: i64) -> Result<()>;
+        fn decline_join_request(&self, group_id: i64, user_id: i64) -> Result<()>;
+        fn join_group(&self, group_id: i64) -> Result<JoinStatus>;
+        fn leave_group(&self, group_id: i64) -> Result<()>;
+        fn get_authenticated_user(&self) -> Result<i64>;
+        fn get_universe(&self, universe_id: i64) -> Result<UniverseInfo>;
+        fn get_group_games(&self, group_id: i64) -> Result<Vec<UniverseInfo>>;
+        fn get_favorite_games(&self, user_id: i64) -> Result<Vec<UniverseInfo>>;
+        fn get_recent_games(&self, user_id: i64) -> Result<Vec<UniverseInfo>>;
+        fn get_servers(&self, place_id: i64, server_type: ServerType) -> Result<Vec<GameServer>>;
+        fn get_server_join_script(&self, place_id: i64, game_id: &str) -> Result<JoinScript>;
+        fn get_universe_gamepasses(&self, universe_id: i64) -> Result<Vec<GamePass>>;
+        fn get_developer_products(&self, universe_id: i64) -> Result<Vec<DeveloperProduct>>;
+        fn get_game_votes(&self, universe_id: i64) -> Result<Votes>;
+        fn place_id_to_universe_id(&self, place_id: i64) -> Result<i64>;
+        fn place_ids_to_universe_ids(&self, place_ids: &[i64]) -> Result<HashMap<i64, i64>>;
+        fn get_group_funds(&self, group_id: i64) -> Result<i64>;
+        fn get_authenticated_balance(&self) -> Result<i64>;
+        fn pay_group_member(&self, group_id: i64, user_id: i64, amount: i64) -> Result<()>;
+        fn set_recurring_payouts(&self, group_id: i64, percentages: &[(i64, i64)]) -> Result<()>;
+        fn get_trades(&self, status: TradeStatus) -> Result<Vec<TradeSummary>>;
+        fn get_trade(&self, trade_id: i64) -> Result<TradeDetail>;
+        fn accept_trade(&self, trade_id: i64) -> Result<()>;
+        fn decline_trade(&self, trade_id: i64) -> Result<()>;
+        fn counter_trade(&self, trade_id: i64, offer: TradeOfferInput) -> Result<i64>;
+        fn get_datastore_entry(&self, universe_id: i64, datastore: &str, key: &str) -> Result<Value>;
+        fn publish_message(&self, universe_id: i64, topic: &str, message: &str) -> Result<()>;
+    }
+}
@@ -1,126 +1,4013 @@
-use reqwest::{Client as ReqwestClient, Error as ReqwestError};
-use serde_json::Value;
-use std::{collections::HashMap, result::Result as StdResult};
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+#[cfg(not(target_arch = "wasm32"))]
+use futures::{future::BoxFuture, stream::BoxStream};
+#[cfg(target_arch = "wasm32")]
+use futures::{future::LocalBoxFuture, stream::LocalBoxStream};
+use futures::{
+    future::{FutureExt, Shared},
+    stream, Future, Stream, StreamExt, TryStreamExt,
+};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use reqwest::{
+    header::{HeaderName, COOKIE, RETRY_AFTER, USER_AGENT},
+    Client as ReqwestClient, Error as ReqwestError, Method, RequestBuilder, Response, StatusCode,
+};
+#[cfg(feature = "chrono")]
+use serde::de::Error as _;
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::{json, Value};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    error::Error,
+    fmt,
+    pin::Pin,
+    result::Result as StdResult,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{Semaphore, SemaphorePermit};
+// std's clock panics in the browser.
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+#[derive(Debug)]
+pub enum RobloxError {
+    Reqwest(ReqwestError),
+    MissingField {
+        url: String,
+        field: &'static str,
+    },
+    Parse(String),
+    /// A non-2xx response with no more specific variant, along with its body.
+    HttpStatus {
+        code: u16,
+        body: String,
+    },
+    RateLimited {
+        retry_after: Option<Duration>,
+    },
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Timeout,
+    NotInGroup,
+    NoAdjacentRole,
+    InsufficientFunds,
+    /// Roblox refused the request with a 400, along with its reason (like
+    /// blocking yourself or someone already blocked).
+    Rejected(String),
+    /// One side of a friend request is already at Roblox's friend cap.
+    TooManyFriends,
+    /// The trade has already been completed, declined or has expired.
+    TradeInactive,
+    /// A transport error from a deduplicated request, handed to every caller
+    /// that was waiting on it.
+    Shared(Arc<RobloxError>),
+}
+
+impl RobloxError {
+    /// Copies an error out of a deduplicated request. Transport errors can't
+    /// be cloned, so those come back wrapped in [`RobloxError::Shared`].
+    fn duplicate(err: &Arc<RobloxError>) -> RobloxError {
+        match &**err {
+            RobloxError::MissingField { url, field } => RobloxError::MissingField {
+                url: url.clone(),
+                field,
+            },
+            RobloxError::Parse(body) => RobloxError::Parse(body.clone()),
+            RobloxError::HttpStatus { code, body } => RobloxError::HttpStatus {
+                code: *code,
+                body: body.clone(),
+            },
+            RobloxError::RateLimited { retry_after } => RobloxError::RateLimited {
+                retry_after: *retry_after,
+            },
+            RobloxError::Unauthorized => RobloxError::Unauthorized,
+            RobloxError::Forbidden => RobloxError::Forbidden,
+            RobloxError::NotFound => RobloxError::NotFound,
+            RobloxError::Timeout => RobloxError::Timeout,
+            RobloxError::NotInGroup => RobloxError::NotInGroup,
+            RobloxError::NoAdjacentRole => RobloxError::NoAdjacentRole,
+            RobloxError::InsufficientFunds => RobloxError::InsufficientFunds,
+            RobloxError::Rejected(message) => RobloxError::Rejected(message.clone()),
+            RobloxError::TooManyFriends => RobloxError::TooManyFriends,
+            RobloxError::TradeInactive => RobloxError::TradeInactive,
+            RobloxError::Reqwest(_) | RobloxError::Shared(_) => {
+                RobloxError::Shared(Arc::clone(err))
+            }
+        }
+    }
+
+    /// Whether the same request might succeed if sent again: timeouts,
+    /// connection failures, server errors and rate limits.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RobloxError::Reqwest(err) => {
+                is_transient(err)
+                    || matches!(err.status(), Some(status) if status.is_server_error())
+            }
+            RobloxError::HttpStatus { code, .. } => *code >= 500,
+            RobloxError::RateLimited { .. } | RobloxError::Timeout => true,
+            RobloxError::Shared(err) => err.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// The HTTP status Roblox answered with, for errors that came from one.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            RobloxError::Reqwest(err) => err.status().map(|status| status.as_u16()),
+            RobloxError::HttpStatus { code, .. } => Some(*code),
+            RobloxError::RateLimited { .. } => Some(429),
+            RobloxError::Unauthorized => Some(401),
+            RobloxError::Forbidden => Some(403),
+            RobloxError::NotFound => Some(404),
+            RobloxError::Shared(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            RobloxError::RateLimited { .. } => true,
+            RobloxError::Shared(err) => err.is_rate_limited(),
+            _ => false,
+        }
+    }
+}
+
+type Result<T> = StdResult<T, RobloxError>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupRole {
+    pub id: i64,
+    pub name: String,
+    pub rank: i64,
+    pub member_count: Option<i64>,
+}
+
+/// A user as the groups API describes them, whether in a roster or as an
+/// owner, poster, requester or audit log actor.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupMember {
+    pub user_id: i64,
+    pub username: String,
+    pub display_name: String,
+}
+
+/// `owner` is `None` for groups that have been abandoned by their owner.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupInfo {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub owner: Option<GroupMember>,
+    pub member_count: i64,
+    pub public_entry_allowed: bool,
+    #[serde(default)]
+    pub is_locked: bool,
+    #[serde(default)]
+    pub has_verified_badge: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserGroupRole {
+    pub group: GroupInfo,
+    pub role: GroupRole,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupSearchResult {
+    pub id: i64,
+    pub name: String,
+    pub member_count: i64,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub has_verified_badge: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shout {
+    pub body: String,
+    pub poster: GroupMember,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub updated: Timestamp,
+}
+
+/// `poster` is `None` when the posting account has since been deleted.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WallPost {
+    pub id: i64,
+    #[serde(default, deserialize_with = "optional_audit_actor")]
+    pub poster: Option<GroupMember>,
+    pub body: String,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+}
+
+/// One audit log entry. The shape of `description` depends on `action_type`
+/// (such as `"Change Rank"` or `"Remove Member"`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    #[serde(deserialize_with = "audit_actor")]
+    pub actor: GroupMember,
+    pub action_type: String,
+    pub description: Value,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+}
+
+#[derive(Deserialize)]
+struct AuditActor {
+    user: GroupMember,
+}
+
+/// Reads the user out of the `{ "user": {...}, "role": {...} }` member objects
+/// used by the audit log and wall posts.
+fn audit_actor<'de, D>(deserializer: D) -> StdResult<GroupMember, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(AuditActor::deserialize(deserializer)?.user)
+}
+
+/// Like [`audit_actor`], mapping a `null` member to `None`.
+fn optional_audit_actor<'de, D>(deserializer: D) -> StdResult<Option<GroupMember>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<AuditActor>::deserialize(deserializer)?.map(|actor| actor.user))
+}
+
+/// A time sent by Roblox. With the `chrono` feature this is parsed into a
+/// [`DateTime<Utc>`]; without it, it's the ISO-8601 string as sent.
+#[cfg(feature = "chrono")]
+pub type Timestamp = DateTime<Utc>;
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = String;
+
+#[cfg(feature = "chrono")]
+fn timestamp<'de, D>(deserializer: D) -> StdResult<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    parse_timestamp(&raw).ok_or_else(|| D::Error::custom(format!("invalid timestamp: {}", raw)))
+}
+
+#[cfg(feature = "chrono")]
+fn optional_timestamp<'de, D>(deserializer: D) -> StdResult<Option<Timestamp>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(raw) => parse_timestamp(&raw)
+            .map(Some)
+            .ok_or_else(|| D::Error::custom(format!("invalid timestamp: {}", raw))),
+        None => Ok(None),
+    }
+}
+
+/// Most endpoints send RFC 3339, but a few older ones leave off the offset
+/// of what is still a UTC time.
+#[cfg(feature = "chrono")]
+fn parse_timestamp(raw: &str) -> Option<Timestamp> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Some(time.with_timezone(&Utc));
+    }
+    NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|time| Utc.from_utc_datetime(&time))
+}
+
+/// Whole days from `created` to `now`, rounded down.
+#[cfg(feature = "chrono")]
+fn days_between(created: DateTime<Utc>, now: DateTime<Utc>) -> i64 {
+    (now - created).num_days()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JoinRequest {
+    pub requester: GroupMember,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinStatus {
+    Joined,
+    /// The group needs a manager to approve the join request first.
+    Pending,
+}
+
+/// Who made a game, asset or bundle. `name` is empty when the endpoint only
+/// returns the creator's id.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RawCreator")]
+pub enum Creator {
+    User { id: i64, name: String },
+    Group { id: i64, name: String },
+}
+
+impl Creator {
+    pub fn id(&self) -> i64 {
+        match self {
+            Creator::User { id, .. } | Creator::Group { id, .. } => *id,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Creator::User { name, .. } | Creator::Group { name, .. } => name,
+        }
+    }
+}
+
+/// Covers both the camelCase `{ id, name, type }` creators and the economy
+/// API's PascalCase `{ CreatorTargetId, Name, CreatorType }` ones.
+#[derive(Deserialize)]
+struct RawCreator {
+    #[serde(alias = "CreatorTargetId")]
+    id: i64,
+    #[serde(default, alias = "Name")]
+    name: String,
+    #[serde(rename = "type", alias = "creatorType", alias = "CreatorType")]
+    creator_type: String,
+}
+
+impl TryFrom<RawCreator> for Creator {
+    type Error = String;
+
+    fn try_from(raw: RawCreator) -> StdResult<Self, Self::Error> {
+        let RawCreator {
+            id,
+            name,
+            creator_type,
+        } = raw;
+        match creator_type.as_str() {
+            "User" => Ok(Creator::User { id, name }),
+            "Group" => Ok(Creator::Group { id, name }),
+            other => Err(format!("unknown creator type {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AssetDetails {
+    #[serde(rename = "AssetId")]
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub creator: Creator,
+    #[serde(rename = "PriceInRobux")]
+    pub price: Option<i64>,
+    #[serde(rename = "AssetTypeId")]
+    pub asset_type: RobloxAssetType,
+}
+
+/// An asset or bundle found through [`Client::search_catalog`]. `item_type`
+/// is `"Asset"` or `"Bundle"`, and `price` is `None` for off-sale items.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawCatalogItem")]
+pub struct CatalogItem {
+    pub id: i64,
+    pub item_type: String,
+    pub name: String,
+    pub price: Option<i64>,
+    pub creator: Creator,
+}
+
+/// Catalog results list their creator as flat fields next to the item's own.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawCatalogItem {
+    id: i64,
+    item_type: String,
+    name: String,
+    price: Option<i64>,
+    creator_type: String,
+    creator_target_id: i64,
+    #[serde(default)]
+    creator_name: String,
+}
+
+impl TryFrom<RawCatalogItem> for CatalogItem {
+    type Error = String;
+
+    fn try_from(raw: RawCatalogItem) -> StdResult<Self, Self::Error> {
+        let creator = Creator::try_from(RawCreator {
+            id: raw.creator_target_id,
+            name: raw.creator_name,
+            creator_type: raw.creator_type,
+        })?;
+
+        Ok(CatalogItem {
+            id: raw.id,
+            item_type: raw.item_type,
+            name: raw.name,
+            price: raw.price,
+            creator,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogSort {
+    Relevance,
+    Favorited,
+    Sales,
+    Updated,
+    PriceAscending,
+    PriceDescending,
+}
+
+impl CatalogSort {
+    fn sort_type(self) -> u8 {
+        match self {
+            CatalogSort::Relevance => 0,
+            CatalogSort::Favorited => 1,
+            CatalogSort::Sales => 2,
+            CatalogSort::Updated => 3,
+            CatalogSort::PriceAscending => 4,
+            CatalogSort::PriceDescending => 5,
+        }
+    }
+}
+
+/// Filters for [`Client::search_catalog`]; anything left unset isn't sent.
+/// `category` and `subcategory` take the catalog's own names, such as
+/// `"Accessories"` and `"HeadAccessories"`.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogQuery {
+    keyword: Option<String>,
+    category: Option<String>,
+    subcategory: Option<String>,
+    sort: Option<CatalogSort>,
+    min_price: Option<i64>,
+    max_price: Option<i64>,
+    limit: Option<usize>,
+}
+
+impl CatalogQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keyword(mut self, keyword: impl Into<String>) -> Self {
+        self.keyword = Some(keyword.into());
+        self
+    }
+
+    pub fn category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    pub fn subcategory(mut self, subcategory: impl Into<String>) -> Self {
+        self.subcategory = Some(subcategory.into());
+        self
+    }
+
+    pub fn sort(mut self, sort: CatalogSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Limits results to items priced between `min` and `max` robux,
+    /// inclusive.
+    pub fn price_range(mut self, min: i64, max: i64) -> Self {
+        self.min_price = Some(min);
+        self.max_price = Some(max);
+        self
+    }
+
+    /// Stops after `limit` results. Without one every page is fetched, which
+    /// for a broad search is a lot of requests.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn query_string(&self) -> String {
+        let mut params = Vec::new();
+        if let Some(keyword) = &self.keyword {
+            params.push(format!("Keyword={}", encode(keyword)));
+        }
+        if let Some(category) = &self.category {
+            params.push(format!("Category={}", encode(category)));
+        }
+        if let Some(subcategory) = &self.subcategory {
+            params.push(format!("Subcategory={}", encode(subcategory)));
+        }
+        if let Some(sort) = self.sort {
+            params.push(format!("SortType={}", sort.sort_type()));
+        }
+        if let Some(min_price) = self.min_price {
+            params.push(format!("MinPrice={}", min_price));
+        }
+        if let Some(max_price) = self.max_price {
+            params.push(format!("MaxPrice={}", max_price));
+        }
+        params.push("Limit=120".to_string());
+
+        params.join("&")
+    }
+}
+
+/// Robux sold for on one day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricePoint {
+    pub value: i64,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub date: Timestamp,
+}
+
+/// Copies sold on one day.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VolumePoint {
+    pub value: i64,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub date: Timestamp,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResaleData {
+    pub recent_average_price: i64,
+    pub original_price: Option<i64>,
+    #[serde(rename = "volumeDataPoints")]
+    pub volume_data: Vec<VolumePoint>,
+    pub price_data_points: Vec<PricePoint>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleItem {
+    pub id: i64,
+    pub name: String,
+    /// `Asset` or `UserOutfit`.
+    #[serde(rename = "type")]
+    pub item_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInfo {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub bundle_type: String,
+    pub items: Vec<BundleItem>,
+    pub creator: Creator,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UniverseInfo {
+    pub id: i64,
+    pub root_place_id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub creator: Creator,
+    /// Only known when fetched through [`Client::get_universe`].
+    pub playing: Option<i64>,
+    pub visits: i64,
+    /// Only known when fetched through [`Client::get_universe`].
+    pub max_players: Option<i64>,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub updated: Timestamp,
+}
+
+/// The slimmer universe model returned by the v2 games endpoints.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GameV2 {
+    id: i64,
+    name: String,
+    description: Option<String>,
+    creator: Creator,
+    root_place: GamePlace,
+    place_visits: i64,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    created: Timestamp,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    updated: Timestamp,
+}
+
+#[derive(Deserialize)]
+struct GamePlace {
+    id: i64,
+}
+
+impl From<GameV2> for UniverseInfo {
+    fn from(game: GameV2) -> Self {
+        UniverseInfo {
+            id: game.id,
+            root_place_id: game.root_place.id,
+            name: game.name,
+            description: game.description,
+            creator: game.creator,
+            playing: None,
+            visits: game.place_visits,
+            max_players: None,
+            created: game.created,
+            updated: game.updated,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Votes {
+    pub up_votes: i64,
+    pub down_votes: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    pub asset_id: i64,
+    pub name: String,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarScales {
+    pub height: f64,
+    pub width: f64,
+    pub head: f64,
+    pub depth: f64,
+    pub proportion: f64,
+    pub body_type: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BodyColors {
+    pub head_color_id: i64,
+    pub torso_color_id: i64,
+    pub right_arm_color_id: i64,
+    pub left_arm_color_id: i64,
+    pub right_leg_color_id: i64,
+    pub left_leg_color_id: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AvatarAsset {
+    pub id: i64,
+    pub name: String,
+    #[serde(deserialize_with = "asset_type_object")]
+    pub asset_type: RobloxAssetType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Outfit {
+    pub id: i64,
+    pub name: String,
+    pub is_editable: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutfitDetails {
+    pub id: i64,
+    pub name: String,
+    pub assets: Vec<AvatarAsset>,
+    pub body_colors: BodyColors,
+    pub player_avatar_type: String,
+    pub is_editable: bool,
+}
+
+/// `assets` holds the items the user is currently wearing.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Avatar {
+    pub scales: AvatarScales,
+    pub body_colors: BodyColors,
+    pub assets: Vec<AvatarAsset>,
+    pub player_avatar_type: String,
+}
+
+/// Reads an asset type from the `{ "id": 8, "name": "Hat" }` objects used by
+/// the avatar endpoints.
+fn asset_type_object<'de, D>(deserializer: D) -> StdResult<RobloxAssetType, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct AssetTypeObject {
+        id: i64,
+    }
+
+    Ok(AssetTypeObject::deserialize(deserializer)?.id.into())
+}
+
+/// `description` and `created` are empty when the user came from the bulk
+/// [`Client::get_users`] lookup, which doesn't return them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserInfo {
+    pub id: i64,
+    pub name: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    /// `None` when Roblox leaves the creation date off.
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, deserialize_with = "optional_timestamp")
+    )]
+    pub created: Option<Timestamp>,
+    #[serde(default)]
+    pub has_verified_badge: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UsernameValidation {
+    Valid,
+    Taken,
+    /// Caught by Roblox's text filter.
+    Censored,
+    /// Breaks one of the other username rules, described by Roblox's message.
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserSearchResult {
+    pub id: i64,
+    pub name: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub previous_usernames: Vec<String>,
+    #[serde(default)]
+    pub has_verified_badge: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Friend {
+    pub id: i64,
+    pub name: String,
+    pub display_name: String,
+    pub is_online: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub enabled: bool,
+}
+
+/// An account-wide badge awarded by Roblox itself, such as Veteran or
+/// Friendship, rather than by a game.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RobloxBadge {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub image_url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "i64")]
+pub enum PresenceType {
+    Offline,
+    Online,
+    InGame,
+    InStudio,
+    Invisible,
+}
+
+impl TryFrom<i64> for PresenceType {
+    type Error = String;
+
+    fn try_from(value: i64) -> StdResult<Self, Self::Error> {
+        match value {
+            0 => Ok(PresenceType::Offline),
+            1 => Ok(PresenceType::Online),
+            2 => Ok(PresenceType::InGame),
+            3 => Ok(PresenceType::InStudio),
+            4 => Ok(PresenceType::Invisible),
+            other => Err(format!("unknown userPresenceType {}", other)),
+        }
+    }
+}
+
+/// The location fields are only populated when the client is authenticated
+/// and allowed to see them, so they are `None` otherwise.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Presence {
+    #[serde(rename = "userPresenceType")]
+    pub presence_type: PresenceType,
+    pub last_location: Option<String>,
+    pub place_id: Option<i64>,
+    pub universe_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetType {
+    Asset,
+    GamePass,
+    Badge,
+    Bundle,
+}
+
+impl AssetType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AssetType::Asset => "Asset",
+            AssetType::GamePass => "GamePass",
+            AssetType::Badge => "Badge",
+            AssetType::Bundle => "Bundle",
+        }
+    }
+}
+
+macro_rules! roblox_asset_types {
+    ($($name:ident = $id:literal,)*) => {
+        /// Roblox catalog asset types, keyed by their numeric asset type id.
+        /// Ids this crate does not know about yet are kept as `Other`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+        #[serde(from = "i64")]
+        pub enum RobloxAssetType {
+            $($name,)*
+            Other(i64),
+        }
+
+        impl RobloxAssetType {
+            pub fn id(&self) -> i64 {
+                match self {
+                    $(RobloxAssetType::$name => $id,)*
+                    RobloxAssetType::Other(id) => *id,
+                }
+            }
+        }
+
+        impl From<i64> for RobloxAssetType {
+            fn from(id: i64) -> Self {
+                match id {
+                    $($id => RobloxAssetType::$name,)*
+                    other => RobloxAssetType::Other(other),
+                }
+            }
+        }
+
+        impl fmt::Display for RobloxAssetType {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(RobloxAssetType::$name => f.write_str(stringify!($name)),)*
+                    RobloxAssetType::Other(id) => write!(f, "Unknown({})", id),
+                }
+            }
+        }
+    };
+}
+
+roblox_asset_types! {
+    Image = 1,
+    TShirt = 2,
+    Audio = 3,
+    Mesh = 4,
+    Lua = 5,
+    Hat = 8,
+    Place = 9,
+    Model = 10,
+    Shirt = 11,
+    Pants = 12,
+    Decal = 13,
+    Head = 17,
+    Face = 18,
+    Gear = 19,
+    Badge = 21,
+    Animation = 24,
+    Torso = 27,
+    RightArm = 28,
+    LeftArm = 29,
+    LeftLeg = 30,
+    RightLeg = 31,
+    Package = 32,
+    GamePass = 34,
+    Plugin = 38,
+    MeshPart = 40,
+    HairAccessory = 41,
+    FaceAccessory = 42,
+    NeckAccessory = 43,
+    ShoulderAccessory = 44,
+    FrontAccessory = 45,
+    BackAccessory = 46,
+    WaistAccessory = 47,
+    ClimbAnimation = 48,
+    DeathAnimation = 49,
+    FallAnimation = 50,
+    IdleAnimation = 51,
+    JumpAnimation = 52,
+    RunAnimation = 53,
+    SwimAnimation = 54,
+    WalkAnimation = 55,
+    PoseAnimation = 56,
+    EarAccessory = 57,
+    EyeAccessory = 58,
+    EmoteAnimation = 61,
+    Video = 62,
+    TShirtAccessory = 64,
+    ShirtAccessory = 65,
+    PantsAccessory = 66,
+    JacketAccessory = 67,
+    SweaterAccessory = 68,
+    ShortsAccessory = 69,
+    LeftShoeAccessory = 70,
+    RightShoeAccessory = 71,
+    DressSkirtAccessory = 72,
+    EyebrowAccessory = 76,
+    EyelashAccessory = 77,
+    MoodAnimation = 78,
+    DynamicHead = 79,
+}
+
+/// `price` is `None` for passes that aren't for sale.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePass {
+    pub id: i64,
+    pub name: String,
+    pub price: Option<i64>,
+    pub seller_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct DeveloperProduct {
+    #[serde(rename = "DeveloperProductId")]
+    pub id: i64,
+    pub product_id: i64,
+    pub name: String,
+    pub price_in_robux: Option<i64>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerType {
+    Public,
+    /// Servers with the authenticated user's friends in them.
+    Friends,
+}
+
+impl ServerType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ServerType::Public => "Public",
+            ServerType::Friends => "Friends",
+        }
+    }
+}
+
+/// A running server. `ping` and `fps` are missing for servers that haven't
+/// reported them yet.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameServer {
+    pub id: String,
+    pub max_players: i64,
+    #[serde(default)]
+    pub playing: i64,
+    pub ping: Option<i64>,
+    pub fps: Option<f64>,
+}
+
+/// What the game-join endpoint hands back for a server. `status` is Roblox's
+/// own join state (2 means the server is ready); the connection details are
+/// only there once it is, with `message` explaining why when they aren't.
+#[derive(Debug, Clone)]
+pub struct JoinScript {
+    pub status: i64,
+    pub job_id: Option<String>,
+    pub join_script_url: Option<String>,
+    pub server_address: Option<String>,
+    pub server_port: Option<i64>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageTab {
+    Inbox,
+    Sent,
+    Archive,
+}
+
+impl MessageTab {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MessageTab::Inbox => "Inbox",
+            MessageTab::Sent => "Sent",
+            MessageTab::Archive => "Archive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateMessage {
+    pub id: i64,
+    pub sender: UserInfo,
+    pub subject: String,
+    pub body: String,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+    pub is_read: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeStatus {
+    Inbound,
+    Outbound,
+    Completed,
+    Inactive,
+}
+
+impl TradeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TradeStatus::Inbound => "Inbound",
+            TradeStatus::Outbound => "Outbound",
+            TradeStatus::Completed => "Completed",
+            TradeStatus::Inactive => "Inactive",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeUser {
+    pub id: i64,
+    pub name: String,
+    pub display_name: String,
+}
+
+/// `user` is the other side of the trade. `status` is Roblox's own state
+/// string, such as `Open`, `Completed` or `Declined`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeSummary {
+    pub id: i64,
+    pub user: TradeUser,
+    #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+    pub created: Timestamp,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, deserialize_with = "optional_timestamp")
+    )]
+    pub expiration: Option<Timestamp>,
+    pub status: String,
+}
+
+/// One copy of an item up for trade. `id` is the user asset ID that trade
+/// requests refer to it by.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserAsset {
+    pub id: i64,
+    pub asset_id: i64,
+    pub name: String,
+    pub recent_average_price: Option<i64>,
+}
+
+/// What one side of a trade puts up.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeOffer {
+    #[serde(rename = "user", deserialize_with = "offer_user_id")]
+    pub user_id: i64,
+    pub user_assets: Vec<UserAsset>,
+    pub robux: i64,
+}
+
+fn offer_user_id<'de, D>(deserializer: D) -> StdResult<i64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct OfferUser {
+        id: i64,
+    }
+
+    Ok(OfferUser::deserialize(deserializer)?.id)
+}
+
+/// `user` is the other side of the trade; `offers` holds both sides.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeDetail {
+    pub id: i64,
+    pub user: UserInfo,
+    pub offers: Vec<TradeOffer>,
+}
+
+/// A trade to propose: what the authenticated user gives up, and what they
+/// want from the other side in return.
+#[derive(Debug, Clone, Default)]
+pub struct TradeOfferInput {
+    pub offered_user_asset_ids: Vec<i64>,
+    pub offered_robux: i64,
+    pub requested_user_asset_ids: Vec<i64>,
+    pub requested_robux: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationshipKind {
+    Allies,
+    Enemies,
+}
+
+impl RelationshipKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RelationshipKind::Allies => "allies",
+            RelationshipKind::Enemies => "enemies",
+        }
+    }
+}
+
+/// Group icons only come in `150x150` and `420x420`; every size is valid for
+/// avatar headshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailSize {
+    Size48x48,
+    Size50x50,
+    Size60x60,
+    Size75x75,
+    Size100x100,
+    Size110x110,
+    Size150x150,
+    Size180x180,
+    Size352x352,
+    Size420x420,
+    Size720x720,
+}
+
+impl ThumbnailSize {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThumbnailSize::Size48x48 => "48x48",
+            ThumbnailSize::Size50x50 => "50x50",
+            ThumbnailSize::Size60x60 => "60x60",
+            ThumbnailSize::Size75x75 => "75x75",
+            ThumbnailSize::Size100x100 => "100x100",
+            ThumbnailSize::Size110x110 => "110x110",
+            ThumbnailSize::Size150x150 => "150x150",
+            ThumbnailSize::Size180x180 => "180x180",
+            ThumbnailSize::Size352x352 => "352x352",
+            ThumbnailSize::Size420x420 => "420x420",
+            ThumbnailSize::Size720x720 => "720x720",
+        }
+    }
+}
+
+impl fmt::Display for ThumbnailSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Api {
+    AccountInformation,
+    AccountSettings,
+    Auth,
+    Avatar,
+    Badges,
+    Catalog,
+    Economy,
+    Friends,
+    GameJoin,
+    Games,
+    Groups,
+    Inventory,
+    OpenCloud,
+    PremiumFeatures,
+    Presence,
+    PrivateMessages,
+    Thumbnails,
+    Trades,
+    Users,
+}
+
+impl Api {
+    const ALL: [Api; 19] = [
+        Api::AccountInformation,
+        Api::AccountSettings,
+        Api::Auth,
+        Api::Avatar,
+        Api::Badges,
+        Api::Catalog,
+        Api::Economy,
+        Api::Friends,
+        Api::GameJoin,
+        Api::Games,
+        Api::Groups,
+        Api::Inventory,
+        Api::OpenCloud,
+        Api::PremiumFeatures,
+        Api::Presence,
+        Api::PrivateMessages,
+        Api::Thumbnails,
+        Api::Trades,
+        Api::Users,
+    ];
+
+    fn default_base_url(self) -> &'static str {
+        match self {
+            Api::AccountInformation => "https://accountinformation.roblox.com",
+            Api::AccountSettings => "https://accountsettings.roblox.com",
+            Api::Auth => "https://auth.roblox.com",
+            Api::Avatar => "https://avatar.roblox.com",
+            Api::Badges => "https://badges.roblox.com",
+            Api::Catalog => "https://catalog.roblox.com",
+            Api::Economy => "https://economy.roblox.com",
+            Api::Friends => "https://friends.roblox.com",
+            Api::GameJoin => "https://gamejoin.roblox.com",
+            Api::Games => "https://games.roblox.com",
+            Api::Groups => "https://groups.roblox.com",
+            Api::Inventory => "https://inventory.roblox.com",
+            Api::OpenCloud => "https://apis.roblox.com",
+            Api::PremiumFeatures => "https://premiumfeatures.roblox.com",
+            Api::Presence => "https://presence.roblox.com",
+            Api::PrivateMessages => "https://privatemessages.roblox.com",
+            Api::Thumbnails => "https://thumbnails.roblox.com",
+            Api::Trades => "https://trades.roblox.com",
+            Api::Users => "https://users.roblox.com",
+        }
+    }
+}
+
+/// Requests a second each family can take without tripping Roblox's own
+/// per-endpoint limits.
+const DEFAULT_ENDPOINT_RATE_LIMITS: [(Api, u32); 5] = [
+    (Api::Groups, 10),
+    (Api::Users, 10),
+    (Api::Inventory, 5),
+    (Api::Thumbnails, 20),
+    (Api::Presence, 5),
+];
+
+#[derive(Clone, Copy, Debug)]
+struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Exponential backoff with jitter: somewhere between half and all of
+    /// `base_delay * 2^attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        delay.mul_f64(rand::random_range(0.5..=1.0))
+    }
+}
+
+/// A token bucket refilled at `rate` permits a second and holding at most one
+/// second's worth, so short bursts go through without waiting.
+struct RateLimiter {
+    rate: f64,
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: u32) -> Self {
+        let rate = f64::from(requests_per_second);
+        Self {
+            rate,
+            bucket: Mutex::new((rate, Instant::now())),
+        }
+    }
+
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut bucket = self.bucket.lock().unwrap();
+                let (tokens, refilled) = &mut *bucket;
+                let now = Instant::now();
+                let elapsed = now.duration_since(*refilled).as_secs_f64();
+                *tokens = (*tokens + elapsed * self.rate).min(self.rate);
+                *refilled = now;
+                if *tokens >= 1.0 {
+                    *tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - *tokens) / self.rate)
+            };
+            sleep(wait).await;
+        }
+    }
+}
+
+/// Hooks called around every HTTP request, to feed a metrics system. Retries
+/// and CSRF refreshes show up as separate requests. `url` is the full request
+/// URL, ids and query string included. Every hook does nothing by default.
+pub trait RequestObserver: Send + Sync {
+    fn on_request(&self, url: &str) {
+        let _ = url;
+    }
+
+    /// Called for every response, whatever its status.
+    fn on_response(&self, url: &str, status: u16, elapsed: Duration) {
+        let _ = (url, status, elapsed);
+    }
+
+    /// Called when a request fails without a response, after any retries.
+    fn on_error(&self, url: &str, err: &RobloxError) {
+        let _ = (url, err);
+    }
+}
+
+const CSRF_TOKEN: HeaderName = HeaderName::from_static("x-csrf-token");
+const API_KEY: HeaderName = HeaderName::from_static("x-api-key");
+const PARSE_SNIPPET_LEN: usize = 200;
+const DEFAULT_RATE_LIMIT_DELAY: Duration = Duration::from_secs(5);
+const DEFAULT_MAX_RATE_LIMIT_DELAY: Duration = Duration::from_secs(30);
+const DEFAULT_CONCURRENCY: usize = 10;
+const DEFAULT_USER_AGENT: &str = concat!("roblox-rs/", env!("CARGO_PKG_VERSION"));
+
+pub struct ClientBuilder {
+    client: Option<ReqwestClient>,
+    #[cfg(not(target_arch = "wasm32"))]
+    proxies: Vec<String>,
+    base_urls: HashMap<Api, String>,
+    retry: Option<RetryPolicy>,
+    max_rate_limit_delay: Option<Duration>,
+    cookie: Option<String>,
+    api_key: Option<String>,
+    timeout: Option<Duration>,
+    concurrency: usize,
+    role_cache_ttl: Option<Duration>,
+    user_agent: String,
+    rate_limit: Option<u32>,
+    endpoint_rate_limits: HashMap<Api, u32>,
+    dedupe_requests: bool,
+    observer: Option<Arc<dyn RequestObserver>>,
+    max_concurrent: Option<usize>,
+    exclude_banned_users: bool,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self {
+            client: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            proxies: Vec::new(),
+            base_urls: HashMap::new(),
+            retry: None,
+            max_rate_limit_delay: Some(DEFAULT_MAX_RATE_LIMIT_DELAY),
+            cookie: None,
+            api_key: None,
+            timeout: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            role_cache_ttl: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            rate_limit: None,
+            endpoint_rate_limits: HashMap::new(),
+            dedupe_requests: false,
+            observer: None,
+            max_concurrent: None,
+            exclude_banned_users: false,
+        }
+    }
+}
+
+impl ClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends every request through `client` instead of a default one, so its
+    /// connection pool, timeouts, proxies and TLS settings are shared.
+    pub fn reqwest_client(mut self, client: ReqwestClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Overrides the host used for every request to `api`, e.g. to point the
+    /// client at a mock server. Defaults to the production Roblox host.
+    pub fn base_url(mut self, api: Api, url: impl Into<String>) -> Self {
+        let url = url.into().trim_end_matches('/').to_string();
+        self.base_urls.insert(api, url);
+        self
+    }
+
+    /// Retries GET requests that fail to connect or return a 5xx status, up
+    /// to `max_attempts` attempts in total, backing off exponentially from
+    /// `base_delay`. Disabled by default.
+    pub fn with_retries(mut self, max_attempts: u32, base_delay: Duration) -> Self {
+        self.retry = Some(RetryPolicy {
+            max_attempts,
+            base_delay,
+        });
+        self
+    }
+
+    /// Caps how long the client will sleep before retrying a rate-limited
+    /// (429) request. The `Retry-After` header is honoured up to this limit.
+    pub fn max_rate_limit_delay(mut self, max: Duration) -> Self {
+        self.max_rate_limit_delay = Some(max);
+        self
+    }
+
+    /// Returns [`RobloxError::RateLimited`] straight away on a 429 instead of
+    /// sleeping and retrying once.
+    pub fn disable_rate_limit_retry(mut self) -> Self {
+        self.max_rate_limit_delay = None;
+        self
+    }
+
+    /// Authenticates every request with the given `.ROBLOSECURITY` cookie
+    /// value (without the `.ROBLOSECURITY=` prefix). Has no effect on wasm,
+    /// where the browser drops a manually set `Cookie` header.
+    pub fn cookie(mut self, roblosecurity: impl Into<String>) -> Self {
+        self.cookie = Some(roblosecurity.into());
+        self
+    }
+
+    /// Sends an Open Cloud API key in the `x-api-key` header of requests to
+    /// [`Api::OpenCloud`]. Other hosts never receive it.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Fails any request that takes longer than `timeout` with
+    /// [`RobloxError::Timeout`]. Also applies to a client passed in through
+    /// [`ClientBuilder::reqwest_client`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// How many requests methods that fan out over many ids, such as
+    /// [`Client::get_ranks_for_users`], keep in flight at once. Defaults to 10.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Caches each group's role list for `ttl`, so role lookups and
+    /// promotions skip refetching it. Disabled by default; see
+    /// [`Client::invalidate_role_cache`].
+    pub fn role_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.role_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Overrides the `User-Agent` sent with every request, which defaults to
+    /// `roblox-rs/<version>`.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Caps outgoing requests at `requests_per_second`, waiting for a slot
+    /// before each one. The limit is shared by every clone of the built
+    /// client. Unlimited by default.
+    pub fn rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.rate_limit = Some(requests_per_second.max(1));
+        self
+    }
+
+    /// Gives the groups, users, inventory, thumbnails and presence APIs a
+    /// bucket each, so heavy use of one doesn't hold up the others. Limits
+    /// already set with [`ClientBuilder::endpoint_rate_limit`] are kept.
+    pub fn endpoint_rate_limits(mut self) -> Self {
+        for &(api, limit) in DEFAULT_ENDPOINT_RATE_LIMITS.iter() {
+            self.endpoint_rate_limits.entry(api).or_insert(limit);
+        }
+        self
+    }
+
+    /// Caps requests to one API family at `requests_per_second`, on top of
+    /// any overall [`ClientBuilder::rate_limit`].
+    pub fn endpoint_rate_limit(mut self, api: Api, requests_per_second: u32) -> Self {
+        self.endpoint_rate_limits
+            .insert(api, requests_per_second.max(1));
+        self
+    }
+
+    /// Whether [`Client::get_users`] and [`Client::get_ids_from_usernames`]
+    /// leave banned accounts out when a call doesn't say. Banned accounts are
+    /// included by default, as they are by Roblox.
+    pub fn exclude_banned_users(mut self, exclude: bool) -> Self {
+        self.exclude_banned_users = exclude;
+        self
+    }
+
+    /// Lets concurrent lookups of the same URL share a single request, with
+    /// every caller getting a copy of the result. Only applies to reads.
+    pub fn dedupe_requests(mut self) -> Self {
+        self.dedupe_requests = true;
+        self
+    }
+
+    /// Caps how many requests, across every clone of the built client, can be
+    /// waiting on a response at once. Unbounded by default.
+    pub fn max_concurrent(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent = Some(max_concurrent.max(1));
+        self
+    }
+
+    /// Reports every request the client sends to `observer`, for metrics.
+    pub fn observer(mut self, observer: impl RequestObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Sends requests through each of `proxies` in turn, moving on to the next
+    /// one and retrying once when a proxy can't be reached. Every proxy gets
+    /// its own connection pool, so this replaces
+    /// [`ClientBuilder::reqwest_client`]. Not available on wasm.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxies(mut self, proxies: Vec<String>) -> Self {
+        self.proxies = proxies;
+        self
+    }
+
+    pub fn build(mut self) -> Result<Client> {
+        let clients = self.http_clients()?;
+        Ok(Client {
+            clients: Arc::new(clients),
+            next_client: Arc::default(),
+            base_urls: Arc::new(self.base_urls),
+            retry: self.retry,
+            max_rate_limit_delay: self.max_rate_limit_delay,
+            cookie: self.cookie,
+            api_key: self.api_key,
+            timeout: self.timeout,
+            concurrency: self.concurrency,
+            role_cache_ttl: self.role_cache_ttl,
+            role_cache: Arc::default(),
+            user_agent: self.user_agent,
+            csrf_token: Arc::default(),
+            rate_limiter: self.rate_limit.map(|rate| Arc::new(RateLimiter::new(rate))),
+            endpoint_limiters: Arc::new(
+                self.endpoint_rate_limits
+                    .into_iter()
+                    .map(|(api, rate)| (api, RateLimiter::new(rate)))
+                    .collect(),
+            ),
+            in_flight: if self.dedupe_requests {
+                Some(Arc::default())
+            } else {
+                None
+            },
+            observer: self.observer,
+            in_flight_limit: self
+                .max_concurrent
+                .map(|limit| Arc::new(Semaphore::new(limit))),
+            exclude_banned_users: self.exclude_banned_users,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn http_clients(&mut self) -> Result<Vec<ReqwestClient>> {
+        if self.proxies.is_empty() {
+            return Ok(vec![self.client.take().unwrap_or_default()]);
+        }
+        self.proxies
+            .iter()
+            .map(|proxy| {
+                let proxy = Proxy::all(proxy.as_str())?;
+                Ok(ReqwestClient::builder().proxy(proxy).build()?)
+            })
+            .collect()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn http_clients(&mut self) -> Result<Vec<ReqwestClient>> {
+        Ok(vec![self.client.take().unwrap_or_default()])
+    }
+}
+
+type RoleCache = HashMap<i64, (Instant, Vec<GroupRole>)>;
+type SharedBody = StdResult<String, Arc<RobloxError>>;
+#[cfg(not(target_arch = "wasm32"))]
+type InFlight = HashMap<String, Shared<BoxFuture<'static, SharedBody>>>;
+#[cfg(target_arch = "wasm32")]
+type InFlight = HashMap<String, Shared<LocalBoxFuture<'static, SharedBody>>>;
+
+#[derive(Clone)]
+pub struct Client {
+    clients: Arc<Vec<ReqwestClient>>,
+    next_client: Arc<AtomicUsize>,
+    base_urls: Arc<HashMap<Api, String>>,
+    retry: Option<RetryPolicy>,
+    max_rate_limit_delay: Option<Duration>,
+    cookie: Option<String>,
+    api_key: Option<String>,
+    timeout: Option<Duration>,
+    concurrency: usize,
+    role_cache_ttl: Option<Duration>,
+    role_cache: Arc<RwLock<RoleCache>>,
+    user_agent: String,
+    csrf_token: Arc<RwLock<Option<String>>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    endpoint_limiters: Arc<HashMap<Api, RateLimiter>>,
+    in_flight: Option<Arc<Mutex<InFlight>>>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    in_flight_limit: Option<Arc<Semaphore>>,
+    exclude_banned_users: bool,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        ClientBuilder::new()
+            .build()
+            .expect("the default roblox client should always build")
+    }
+}
+
+impl Client {
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    pub fn with_reqwest(client: ReqwestClient) -> Self {
+        ClientBuilder::new()
+            .reqwest_client(client)
+            .build()
+            .expect("a roblox client around an existing reqwest client should always build")
+    }
+
+    /// Drops the cached role list for a group so the next lookup refetches it.
+    pub fn invalidate_role_cache(&self, group_id: i64) {
+        self.role_cache.write().unwrap().remove(&group_id);
+    }
+
+    /// Which API family a caller-supplied URL belongs to, going by the
+    /// longest configured base URL it starts with. Families sharing a base
+    /// URL resolve to whichever comes first in [`Api`].
+    fn api_for(&self, url: &str) -> Option<Api> {
+        let mut best: Option<(Api, usize)> = None;
+        for &api in Api::ALL.iter() {
+            let base = self.base_url(api);
+            let on_host = url.starts_with(base)
+                && matches!(
+                    url[base.len()..].chars().next(),
+                    None | Some('/') | Some('?')
+                );
+            if on_host && !matches!(best, Some((_, len)) if len >= base.len()) {
+                best = Some((api, base.len()));
+            }
+        }
+        best.map(|(api, _)| api)
+    }
+
+    /// The host requests to `api` go to, without a trailing slash. Honours
+    /// [`ClientBuilder::base_url`] overrides.
+    pub fn base_url(&self, api: Api) -> &str {
+        self.base_urls
+            .get(&api)
+            .map(String::as_str)
+            .unwrap_or_else(|| api.default_base_url())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_user_roles(&self, roblox_id: i64) -> Result<HashMap<i64, i64>> {
+        let (url, memberships) = self.get_memberships(roblox_id).await?;
+
+        let mut ranks = HashMap::new();
+        for membership in memberships.iter() {
+            let group_id = membership["group"]["id"]
+                .as_i64()
+                .ok_or_else(|| missing_field(&url, "group.id"))?;
+            let role_rank = membership["role"]["rank"]
+                .as_i64()
+                .ok_or_else(|| missing_field(&url, "role.rank"))?;
+            ranks.insert(group_id, role_rank);
+        }
+        Ok(ranks)
+    }
+
+    /// Lists every group the user is in along with their role in it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_user_group_roles(&self, user_id: i64) -> Result<Vec<UserGroupRole>> {
+        let (url, memberships) = self.get_memberships(user_id).await?;
+
+        parse(&url, Value::Array(memberships))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_user_rank_in_group(&self, user_id: i64, group_id: i64) -> Result<Option<i64>> {
+        let (url, memberships) = self.get_memberships(user_id).await?;
+
+        match memberships
+            .iter()
+            .find(|r| r["group"]["id"].as_i64() == Some(group_id))
+        {
+            Some(role) => Ok(Some(
+                role["role"]["rank"]
+                    .as_i64()
+                    .ok_or_else(|| missing_field(&url, "role.rank"))?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Counts the groups the user is in. Roblox has no dedicated count
+    /// endpoint, so this still fetches the (unpaginated) membership list.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_count(&self, user_id: i64) -> Result<i64> {
+        let (_, memberships) = self.get_memberships(user_id).await?;
+
+        Ok(memberships.len() as i64)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn is_in_group(&self, user_id: i64, group_id: i64) -> Result<bool> {
+        Ok(self
+            .get_user_rank_in_group(user_id, group_id)
+            .await?
+            .is_some())
+    }
+
+    /// Whether the user is in the group with a rank of at least `min_rank`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn is_ranked_at_least(
+        &self,
+        user_id: i64,
+        group_id: i64,
+        min_rank: i64,
+    ) -> Result<bool> {
+        let rank = self.get_user_rank_in_group(user_id, group_id).await?;
+
+        Ok(rank.is_some_and(|rank| rank >= min_rank))
+    }
+
+    /// Looks up each user's rank in the group with one request per user, at
+    /// most [`ClientBuilder::concurrency`] at a time. Fails with the first
+    /// error encountered.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_ranks_for_users(
+        &self,
+        user_ids: &[i64],
+        group_id: i64,
+    ) -> Result<HashMap<i64, Option<i64>>> {
+        self.for_each_id(user_ids, |user_id| {
+            self.get_user_rank_in_group(user_id, group_id)
+        })
+        .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_user_info(&self, roblox_id: i64) -> Result<UserInfo> {
+        let url = format!("{}/v1/users/{}", self.base_url(Api::Users), roblox_id);
+        let body = self.get_value(Some(Api::Users), &url).await?;
+
+        parse(&url, body)
+    }
+
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_account_created(&self, user_id: i64) -> Result<DateTime<Utc>> {
+        self.get_user_info(user_id).await?.created.ok_or_else(|| {
+            let url = format!("{}/v1/users/{}", self.base_url(Api::Users), user_id);
+            missing_field(&url, "created")
+        })
+    }
+
+    /// Whole days since the account was created, rounded down.
+    #[cfg(feature = "chrono")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn account_age_days(&self, user_id: i64) -> Result<i64> {
+        let created = self.get_account_created(user_id).await?;
+
+        Ok(days_between(created, Utc::now()))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_username_from_id(&self, roblox_id: i64) -> Result<String> {
+        Ok(self.get_user_info(roblox_id).await?.name)
+    }
+
+    /// A single-name [`Client::get_ids_from_usernames`], so banned accounts
+    /// follow the client's default.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_id_from_username(&self, username: &str) -> Result<Option<i64>> {
+        let mut ids = self.get_ids_from_usernames(&[username], None).await?;
+
+        Ok(ids.remove(username))
+    }
+
+    /// Checks a would-be username against Roblox's signup rules. `birthday` is
+    /// an ISO-8601 date, since some rules depend on the account's age.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn validate_username(
+        &self,
+        username: &str,
+        birthday: &str,
+    ) -> Result<UsernameValidation> {
+        let url = format!(
+            "{}/v1/usernames/validate?request.username={}&request.birthday={}",
+            self.base_url(Api::Auth),
+            encode(username),
+            encode(birthday)
+        );
+        let body = self.get_value(Some(Api::Auth), &url).await?;
+        let code = body["code"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "code"))?;
+
+        Ok(match code {
+            0 => UsernameValidation::Valid,
+            1 => UsernameValidation::Taken,
+            2 => UsernameValidation::Censored,
+            _ => UsernameValidation::Invalid(
+                body["message"].as_str().unwrap_or_default().to_string(),
+            ),
+        })
+    }
+
+    /// `exclude_banned_users` overrides the client's default for this call.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_ids_from_usernames(
+        &self,
+        usernames: &[&str],
+        exclude_banned_users: Option<bool>,
+    ) -> Result<HashMap<String, i64>> {
+        let url = format!("{}/v1/usernames/users", self.base_url(Api::Users));
+        let request = json!({
+            "usernames": usernames,
+            "excludeBannedUsers": exclude_banned_users.unwrap_or(self.exclude_banned_users),
+        });
+        let body = self.post_value(Some(Api::Users), &url, &request).await?;
+
+        let mut ids = HashMap::new();
+        if let Some(data) = body["data"].as_array() {
+            for user in data.iter() {
+                let username = user["requestedUsername"]
+                    .as_str()
+                    .ok_or_else(|| missing_field(&url, "requestedUsername"))?;
+                let id = user["id"]
+                    .as_i64()
+                    .ok_or_else(|| missing_field(&url, "id"))?;
+                ids.insert(username.to_string(), id);
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Lists the user's previous usernames, most recent first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_username_history(&self, user_id: i64) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct PreviousName {
+            name: String,
+        }
+
+        let url = format!(
+            "{}/v1/users/{}/username-history?limit=100&sortOrder=Desc",
+            self.base_url(Api::Users),
+            user_id
+        );
+
+        self.cursor_pages::<PreviousName>(Some(Api::Users), url)
+            .map_ok(|previous| previous.name)
+            .try_collect()
+            .await
+    }
+
+    /// Looks up many users at once, 100 per request. Ids that don't belong to
+    /// an account are left out of the map. `exclude_banned_users` overrides
+    /// the client's default for this call.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_users(
+        &self,
+        user_ids: &[i64],
+        exclude_banned_users: Option<bool>,
+    ) -> Result<HashMap<i64, UserInfo>> {
+        let url = format!("{}/v1/users", self.base_url(Api::Users));
+        let exclude_banned_users = exclude_banned_users.unwrap_or(self.exclude_banned_users);
+        let mut users = HashMap::new();
+        for chunk in user_ids.chunks(100) {
+            let request = json!({ "userIds": chunk, "excludeBannedUsers": exclude_banned_users });
+            let mut body = self.post_value(Some(Api::Users), &url, &request).await?;
+            if body["data"].is_array() {
+                let page: Vec<UserInfo> = parse(&url, body["data"].take())?;
+                users.extend(page.into_iter().map(|user| (user.id, user)));
+            }
+        }
+        Ok(users)
+    }
+
+    /// Roblox only accepts a `limit` of 10, 25, 50 or 100. This endpoint is
+    /// heavily rate limited, so expect [`RobloxError::RateLimited`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn search_users(&self, keyword: &str, limit: u32) -> Result<Vec<UserSearchResult>> {
+        let url = format!(
+            "{}/v1/users/search?keyword={}&limit={}",
+            self.base_url(Api::Users),
+            encode(keyword),
+            limit
+        );
+        let mut body = self.get_value(Some(Api::Users), &url).await?;
+        if !body["data"].is_array() {
+            return Ok(Vec::new());
+        }
+
+        parse(&url, body["data"].take())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_presences(&self, user_ids: &[i64]) -> Result<HashMap<i64, Presence>> {
+        let url = format!("{}/v1/presence/users", self.base_url(Api::Presence));
+        let request = json!({ "userIds": user_ids });
+        let body = self.post_value(Some(Api::Presence), &url, &request).await?;
+
+        let mut presences = HashMap::new();
+        if let Some(data) = body["userPresences"].as_array() {
+            for presence in data.iter() {
+                let user_id = presence["userId"]
+                    .as_i64()
+                    .ok_or_else(|| missing_field(&url, "userId"))?;
+                presences.insert(user_id, parse(&url, presence.clone())?);
+            }
+        }
+        Ok(presences)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_friends(&self, user_id: i64) -> Result<Vec<Friend>> {
+        let url = format!(
+            "{}/v1/users/{}/friends",
+            self.base_url(Api::Friends),
+            user_id
+        );
+        let mut body = self.get_value(Some(Api::Friends), &url).await?;
+        if !body["data"].is_array() {
+            return Ok(Vec::new());
+        }
+
+        parse(&url, body["data"].take())
+    }
+
+    /// Checks `other_user_id` against the friends list of `user_id`. Roblox's
+    /// direct friend-status endpoint needs the asking account to be one of the
+    /// two users, so this costs one request for the whole (capped) list.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn are_friends(&self, user_id: i64, other_user_id: i64) -> Result<bool> {
+        let friends = self.get_friends(user_id).await?;
+
+        Ok(friends.iter().any(|friend| friend.id == other_user_id))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_friend_count(&self, user_id: i64) -> Result<i64> {
+        self.get_friends_count(user_id, "friends").await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_follower_count(&self, user_id: i64) -> Result<i64> {
+        self.get_friends_count(user_id, "followers").await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_following_count(&self, user_id: i64) -> Result<i64> {
+        self.get_friends_count(user_id, "followings").await
+    }
+
+    /// Fails with [`RobloxError::TooManyFriends`] when either account is at
+    /// the friend cap.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn send_friend_request(&self, user_id: i64) -> Result<()> {
+        self.friend_request_action(user_id, "request-friendship")
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn accept_friend_request(&self, user_id: i64) -> Result<()> {
+        self.friend_request_action(user_id, "accept-friend-request")
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn decline_friend_request(&self, user_id: i64) -> Result<()> {
+        self.friend_request_action(user_id, "decline-friend-request")
+            .await
+    }
+
+    /// Everyone with a pending friend request to the authenticated user.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_friend_requests(&self) -> Result<Vec<UserInfo>> {
+        let url = format!(
+            "{}/v1/my/friends/requests?limit=100",
+            self.base_url(Api::Friends)
+        );
+        self.paginate(Some(Api::Friends), url).await
+    }
+
+    /// Blocks the user for the authenticated account. Blocking yourself or
+    /// someone already blocked fails with [`RobloxError::Rejected`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn block_user(&self, user_id: i64) -> Result<()> {
+        self.set_blocked(user_id, "block").await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn unblock_user(&self, user_id: i64) -> Result<()> {
+        self.set_blocked(user_id, "unblock").await
+    }
+
+    /// The IDs of everyone the authenticated account has blocked.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_blocked_users(&self) -> Result<Vec<i64>> {
+        let url = format!(
+            "{}/v1/users/get-detailed-blocked-users",
+            self.base_url(Api::AccountSettings)
+        );
+        let body = self.get_value(Some(Api::AccountSettings), &url).await?;
+
+        let mut blocked = Vec::new();
+        if let Some(users) = body["blockedUsers"].as_array() {
+            for user in users.iter() {
+                let user_id = user["userId"]
+                    .as_i64()
+                    .ok_or_else(|| missing_field(&url, "userId"))?;
+                blocked.push(user_id);
+            }
+        }
+        Ok(blocked)
+    }
+
+    /// Every message in one of the authenticated user's tabs, newest first.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_messages(&self, tab: MessageTab) -> Result<Vec<PrivateMessage>> {
+        let mut messages = Vec::new();
+        for page in 0.. {
+            let url = format!(
+                "{}/v1/messages?messageTab={}&pageNumber={}&pageSize=20",
+                self.base_url(Api::PrivateMessages),
+                tab.as_str(),
+                page
+            );
+            let mut body = self.get_value(Some(Api::PrivateMessages), &url).await?;
+            if body["collection"].is_array() {
+                messages.extend(parse::<Vec<PrivateMessage>>(
+                    &url,
+                    body["collection"].take(),
+                )?);
+            }
+            if body["totalPages"].as_i64().unwrap_or(0) <= page + 1 {
+                break;
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Roblox reports a refused message (like one to a user who doesn't accept
+    /// messages) in a 200 body, which comes back as [`RobloxError::Rejected`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn send_message(&self, recipient_id: i64, subject: &str, body: &str) -> Result<()> {
+        let user_id = self.get_authenticated_user().await?;
+        let url = format!("{}/v1/messages/send", self.base_url(Api::PrivateMessages));
+        let request = json!({
+            "userId": user_id,
+            "recipientId": recipient_id,
+            "subject": subject,
+            "body": body,
+        });
+        let resp = self
+            .post_value(Some(Api::PrivateMessages), &url, &request)
+            .await?;
+        if resp["success"].as_bool() == Some(false) {
+            let message = resp["message"].as_str().unwrap_or_default();
+            return Err(RobloxError::Rejected(message.to_string()));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_player_badges(&self, user_id: i64) -> Result<Vec<Badge>> {
+        let url = format!(
+            "{}/v1/users/{}/badges?limit=100",
+            self.base_url(Api::Badges),
+            user_id
+        );
+
+        self.paginate(Some(Api::Badges), url).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn has_badge(&self, user_id: i64, badge_id: i64) -> Result<bool> {
+        let url = format!(
+            "{}/v1/users/{}/badges/awarded-dates?badgeIds={}",
+            self.base_url(Api::Badges),
+            user_id,
+            badge_id
+        );
+        let body = self.get_value(Some(Api::Badges), &url).await?;
+        if let Some(data) = body["data"].as_array() {
+            return Ok(!data.is_empty());
+        }
+        Ok(false)
+    }
+
+    /// When the user was awarded each of `badge_ids`. Badges they don't have
+    /// are left out of the map.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_badge_award_dates(
+        &self,
+        user_id: i64,
+        badge_ids: &[i64],
+    ) -> Result<HashMap<i64, Timestamp>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AwardedDate {
+            badge_id: i64,
+            #[cfg_attr(feature = "chrono", serde(deserialize_with = "timestamp"))]
+            awarded_date: Timestamp,
+        }
+
+        let ids = badge_ids
+            .iter()
+            .map(i64::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = format!(
+            "{}/v1/users/{}/badges/awarded-dates?badgeIds={}",
+            self.base_url(Api::Badges),
+            user_id,
+            ids
+        );
+        let mut body = self.get_value(Some(Api::Badges), &url).await?;
+        if !body["data"].is_array() {
+            return Ok(HashMap::new());
+        }
+        let dates: Vec<AwardedDate> = parse(&url, body["data"].take())?;
+
+        Ok(dates
+            .into_iter()
+            .map(|date| (date.badge_id, date.awarded_date))
+            .collect())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_roblox_badges(&self, user_id: i64) -> Result<Vec<RobloxBadge>> {
+        let url = format!(
+            "{}/v1/users/{}/roblox-badges",
+            self.base_url(Api::AccountInformation),
+            user_id
+        );
+        let body = self.get_value(Some(Api::AccountInformation), &url).await?;
+
+        parse(&url, body)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_avatar(&self, user_id: i64) -> Result<Avatar> {
+        let url = format!("{}/v1/users/{}/avatar", self.base_url(Api::Avatar), user_id);
+        let body = self.get_value(Some(Api::Avatar), &url).await?;
+
+        parse(&url, body)
+    }
+
+    /// The outfits endpoint pages by number rather than cursor, so this walks
+    /// pages of 50 until one comes back short.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_outfits(&self, user_id: i64) -> Result<Vec<Outfit>> {
+        let url = format!(
+            "{}/v1/users/{}/outfits",
+            self.base_url(Api::Avatar),
+            user_id
+        );
+
+        self.numbered_pages(Some(Api::Avatar), url, 50)
+            .try_collect()
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_outfit_details(&self, outfit_id: i64) -> Result<OutfitDetails> {
+        let url = format!(
+            "{}/v1/outfits/{}/details",
+            self.base_url(Api::Avatar),
+            outfit_id
+        );
+        let body = self.get_value(Some(Api::Avatar), &url).await?;
+
+        parse(&url, body)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_avatar_headshots(
+        &self,
+        user_ids: &[i64],
+        size: ThumbnailSize,
+    ) -> Result<HashMap<i64, Option<String>>> {
+        self.get_thumbnails("/v1/users/avatar-headshot", "userIds", user_ids, size)
+            .await
+    }
+
+    /// Full-body avatar renders.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_avatar_thumbnails(
+        &self,
+        user_ids: &[i64],
+        size: ThumbnailSize,
+    ) -> Result<HashMap<i64, Option<String>>> {
+        self.get_thumbnails("/v1/users/avatar", "userIds", user_ids, size)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_outfit_thumbnails(
+        &self,
+        outfit_ids: &[i64],
+        size: ThumbnailSize,
+    ) -> Result<HashMap<i64, Option<String>>> {
+        self.get_thumbnails("/v1/users/outfits", "userOutfitIds", outfit_ids, size)
+            .await
+    }
+
+    /// Returns the URL of a JSON manifest pointing at the asset's `.obj`,
+    /// `.mtl` and texture files, or `None` while it is still being rendered.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_asset_thumbnail_3d(&self, asset_id: i64) -> Result<Option<String>> {
+        let url = format!(
+            "{}/v1/assets-thumbnail-3d?assetId={}",
+            self.base_url(Api::Thumbnails),
+            asset_id
+        );
+        let body = self.get_value(Some(Api::Thumbnails), &url).await?;
+
+        Ok(completed_image_url(&body))
+    }
+
+    /// Thumbnails that are still being rendered map to `None`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_icons(
+        &self,
+        group_ids: &[i64],
+        size: ThumbnailSize,
+    ) -> Result<HashMap<i64, Option<String>>> {
+        self.get_thumbnails("/v1/groups/icons", "groupIds", group_ids, size)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn has_asset(
+        &self,
+        roblox_id: i64,
+        item: i64,
+        asset_type: AssetType,
+    ) -> Result<bool> {
+        self.owns_item(roblox_id, item, asset_type.as_str()).await
+    }
+
+    #[deprecated(note = "use `has_asset` with an `AssetType` instead")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn has_asset_raw(&self, roblox_id: i64, item: i64, asset_type: &str) -> Result<bool> {
+        self.owns_item(roblox_id, item, asset_type).await
+    }
+
+    /// Lists every item of `asset_type` the user owns. Private inventories
+    /// fail with [`RobloxError::Forbidden`] rather than looking empty.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_inventory(
+        &self,
+        user_id: i64,
+        asset_type: RobloxAssetType,
+    ) -> Result<Vec<InventoryItem>> {
+        let url = format!(
+            "{}/v2/users/{}/inventory/{}?limit=100",
+            self.base_url(Api::Inventory),
+            user_id,
+            asset_type.id()
+        );
+
+        self.paginate(Some(Api::Inventory), url).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn owns_gamepass(&self, user_id: i64, gamepass_id: i64) -> Result<bool> {
+        self.has_asset(user_id, gamepass_id, AssetType::GamePass)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_asset_details(&self, asset_id: i64) -> Result<AssetDetails> {
+        let url = format!(
+            "{}/v2/assets/{}/details",
+            self.base_url(Api::Economy),
+            asset_id
+        );
+        let body = self.get_value(Some(Api::Economy), &url).await?;
+
+        parse(&url, body)
+    }
+
+    /// Only limiteds have resale data; anything else is
+    /// [`RobloxError::NotFound`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_resale_data(&self, asset_id: i64) -> Result<ResaleData> {
+        let url = format!(
+            "{}/v1/assets/{}/resale-data",
+            self.base_url(Api::Economy),
+            asset_id
+        );
+        let body = match self.get_value(Some(Api::Economy), &url).await {
+            Err(RobloxError::HttpStatus { code: 400, .. }) => return Err(RobloxError::NotFound),
+            body => body?,
+        };
+
+        parse(&url, body)
+    }
+
+    /// Uses the details flavour of the search endpoint, since the plain one
+    /// only returns IDs.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn search_catalog(&self, query: CatalogQuery) -> Result<Vec<CatalogItem>> {
+        let url = format!(
+            "{}/v1/search/items/details?{}",
+            self.base_url(Api::Catalog),
+            query.query_string()
+        );
+        let results = self.cursor_pages(Some(Api::Catalog), url);
+
+        match query.limit {
+            Some(limit) => results.take(limit).try_collect().await,
+            None => results.try_collect().await,
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_bundle(&self, bundle_id: i64) -> Result<BundleInfo> {
+        let url = format!(
+            "{}/v1/bundles/{}/details",
+            self.base_url(Api::Catalog),
+            bundle_id
+        );
+        let body = self.get_value(Some(Api::Catalog), &url).await?;
+
+        parse(&url, body)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn owns_bundle(&self, user_id: i64, bundle_id: i64) -> Result<bool> {
+        let url = format!(
+            "{}/v1/users/{}/items/Bundle/{}/is-owned",
+            self.base_url(Api::Inventory),
+            user_id,
+            bundle_id
+        );
+        let body = self.get_value(Some(Api::Inventory), &url).await?;
+
+        parse(&url, body)
+    }
+
+    /// Requires an authenticated client; fails with
+    /// [`RobloxError::Unauthorized`] otherwise.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn has_premium(&self, user_id: i64) -> Result<bool> {
+        let url = format!(
+            "{}/v1/users/{}/validate-membership",
+            self.base_url(Api::PremiumFeatures),
+            user_id
+        );
+        let body = self.get_value(Some(Api::PremiumFeatures), &url).await?;
+
+        parse(&url, body)
+    }
+
+    /// Checks whether the user's profile description contains `code`, such as
+    /// one made by [`generate_verification_code`]. The lookup is retried per
+    /// [`ClientBuilder::with_retries`], and a request that still fails is an
+    /// error rather than a `false`. A blank code never matches.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn check_code(&self, roblox_id: i64, code: &str) -> Result<bool> {
+        let code = code.trim();
+        if code.is_empty() {
+            return Ok(false);
+        }
+        let description = self.get_user_info(roblox_id).await?.description;
+
+        Ok(description.contains(code))
+    }
+
+    /// Runs [`Client::check_code`] up to `max_attempts` times (at least once),
+    /// `interval` apart, until the code shows up. Retryable errors just count
+    /// as a failed attempt, unless it was the last one.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn poll_for_code(
+        &self,
+        user_id: i64,
+        code: &str,
+        interval: Duration,
+        max_attempts: u32,
+    ) -> Result<bool> {
+        let max_attempts = max_attempts.max(1);
+        for attempt in 1..=max_attempts {
+            match self.check_code(user_id, code).await {
+                Ok(true) => return Ok(true),
+                Err(err) if !err.is_retryable() || attempt == max_attempts => return Err(err),
+                _ => {}
+            }
+            if attempt < max_attempts {
+                sleep(interval).await;
+            }
+        }
+        Ok(false)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_rank(&self, group_id: i64, rank_id: i64) -> Result<Option<GroupRole>> {
+        let roles = self.get_roles(group_id).await?;
+
+        Ok(roles.into_iter().find(|r| r.rank == rank_id))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_ranks(
+        &self,
+        group_id: i64,
+        min_rank: i64,
+        max_rank: i64,
+    ) -> Result<Vec<GroupRole>> {
+        let roles = self.get_roles(group_id).await?;
+        let ranks = roles
+            .into_iter()
+            .filter(|r| (min_rank..=max_rank).contains(&r.rank))
+            .collect::<Vec<GroupRole>>();
+
+        Ok(ranks)
+    }
+
+    /// The headcount of every role, lowest rank first, without walking the
+    /// roster. Roblox only sends the counts to authenticated requests, so
+    /// without a cookie every count is 0. Served from the role cache when
+    /// one is configured.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_role_member_counts(&self, group_id: i64) -> Result<Vec<(GroupRole, i64)>> {
+        let mut roles = self.get_roles(group_id).await?;
+        roles.sort_by_key(|r| r.rank);
+
+        Ok(roles
+            .into_iter()
+            .map(|role| {
+                let count = role.member_count.unwrap_or(0);
+                (role, count)
+            })
+            .collect())
+    }
+
+    /// Finds the role whose name matches `name`, ignoring case.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_role_by_name(&self, group_id: i64, name: &str) -> Result<Option<GroupRole>> {
+        let roles = self.get_roles(group_id).await?;
+        let name = name.to_lowercase();
+
+        Ok(roles.into_iter().find(|r| r.name.to_lowercase() == name))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_role_by_rank(&self, group_id: i64, rank: i64) -> Result<Option<GroupRole>> {
+        self.get_group_rank(group_id, rank).await
+    }
+
+    /// Fetches every member of a role, 100 at a time. Large groups can take
+    /// hundreds of requests, so this may be slow.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_role_members(&self, group_id: i64, role_id: i64) -> Result<Vec<GroupMember>> {
+        self.role_members_stream(group_id, role_id)
+            .try_collect()
+            .await
+    }
+
+    /// Lazily walks the members of a role, fetching the next page only once
+    /// the previous one has been consumed. A failed page fetch is yielded as
+    /// an error and ends the stream.
+    pub fn role_members_stream(&self, group_id: i64, role_id: i64) -> Paginator<'_, GroupMember> {
+        let url = format!(
+            "{}/v1/groups/{}/roles/{}/users?limit=100",
+            self.base_url(Api::Groups),
+            group_id,
+            role_id
+        );
+
+        self.cursor_pages(Some(Api::Groups), url)
+    }
+
+    /// Lazily walks any cursor-paginated endpoint that returns
+    /// `{ "data": [...], "nextPageCursor": ... }`, for endpoints this crate
+    /// doesn't wrap yet. The cursor is appended to `url`'s query string. As
+    /// with [`Client::get_json`], credentials only go to the client's hosts.
+    pub fn paginator<T>(&self, url: impl Into<String>) -> Paginator<'_, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let url = url.into();
+        self.cursor_pages(self.api_for(&url), url)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group(&self, group_id: i64) -> Result<GroupInfo> {
+        let url = format!("{}/v1/groups/{}", self.base_url(Api::Groups), group_id);
+        let body = self.get_value(Some(Api::Groups), &url).await?;
+
+        parse(&url, body)
+    }
+
+    /// Collects search results until `limit` have been found, or every page
+    /// when `limit` is `None`, which can take many requests for broad terms.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn search_groups(
+        &self,
+        keyword: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<GroupSearchResult>> {
+        let url = format!(
+            "{}/v1/groups/search?keyword={}&limit=100",
+            self.base_url(Api::Groups),
+            encode(keyword)
+        );
+        let results = self.cursor_pages(Some(Api::Groups), url);
+
+        match limit {
+            Some(limit) => results.take(limit).try_collect().await,
+            None => results.try_collect().await,
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_relationships(
+        &self,
+        group_id: i64,
+        kind: RelationshipKind,
+    ) -> Result<Vec<GroupInfo>> {
+        let mut groups = Vec::new();
+        let mut start = 0;
+        loop {
+            let url = format!(
+                "{}/v1/groups/{}/relationships/{}?model.startRowIndex={}&model.maxRows=100",
+                self.base_url(Api::Groups),
+                group_id,
+                kind.as_str(),
+                start
+            );
+            let mut body = self.get_value(Some(Api::Groups), &url).await?;
+            if body["relatedGroups"].is_array() {
+                groups.extend(parse::<Vec<GroupInfo>>(&url, body["relatedGroups"].take())?);
+            }
+            let total = body["totalGroupCount"].as_i64().unwrap_or_default();
+            match body["nextRowIndex"].as_i64() {
+                Some(next) if next > start && next < total => start = next,
+                _ => return Ok(groups),
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_primary_group(&self, user_id: i64) -> Result<Option<GroupInfo>> {
+        let url = format!(
+            "{}/v1/users/{}/groups/primary/role",
+            self.base_url(Api::Groups),
+            user_id
+        );
+        let mut body = self.get_value(Some(Api::Groups), &url).await?;
+
+        parse(&url, body["group"].take())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_shout(&self, group_id: i64) -> Result<Option<Shout>> {
+        let url = format!("{}/v1/groups/{}", self.base_url(Api::Groups), group_id);
+        let mut body = self.get_value(Some(Api::Groups), &url).await?;
+
+        parse(&url, body["shout"].take())
+    }
+
+    /// Replaces the group shout. Fails with [`RobloxError::Forbidden`] when
+    /// the cookie's account cannot post shouts in the group.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn post_shout(&self, group_id: i64, message: &str) -> Result<()> {
+        let url = format!(
+            "{}/v1/groups/{}/status",
+            self.base_url(Api::Groups),
+            group_id
+        );
+        self.send_empty(
+            Some(Api::Groups),
+            Method::PATCH,
+            &url,
+            Some(&json!({ "message": message })),
+        )
+        .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_wall_posts(&self, group_id: i64) -> Result<Vec<WallPost>> {
+        let url = format!(
+            "{}/v2/groups/{}/wall/posts?limit=100&sortOrder=Desc",
+            self.base_url(Api::Groups),
+            group_id
+        );
+
+        self.paginate(Some(Api::Groups), url).await
+    }
+
+    /// Posts `message` to the group wall and returns the new post. Fails with
+    /// [`RobloxError::Forbidden`] without permission to post.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn post_to_wall(&self, group_id: i64, message: &str) -> Result<WallPost> {
+        let url = format!(
+            "{}/v2/groups/{}/wall/posts",
+            self.base_url(Api::Groups),
+            group_id
+        );
+        let body = self
+            .post_value(Some(Api::Groups), &url, &json!({ "body": message }))
+            .await?;
+
+        parse(&url, body)
+    }
+
+    /// Fails with [`RobloxError::Forbidden`] without permission to delete
+    /// wall posts.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn delete_wall_post(&self, group_id: i64, post_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/v1/groups/{}/wall/posts/{}",
+            self.base_url(Api::Groups),
+            group_id,
+            post_id
+        );
+        self.send_empty(Some(Api::Groups), Method::DELETE, &url, None)
+            .await
+    }
+
+    /// Moves the user into the role with id `role_id`. Requires a cookie with
+    /// permission to manage lower ranked members.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_rank(&self, group_id: i64, user_id: i64, role_id: i64) -> Result<()> {
+        let url = format!(
+            "{}/v1/groups/{}/users/{}",
+            self.base_url(Api::Groups),
+            group_id,
+            user_id
+        );
+        self.send_empty(
+            Some(Api::Groups),
+            Method::PATCH,
+            &url,
+            Some(&json!({ "roleId": role_id })),
+        )
+        .await
+    }
+
+    /// Moves the user up to the next role and returns it, failing with
+    /// [`RobloxError::NoAdjacentRole`] when they already hold the top role.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn promote(&self, group_id: i64, user_id: i64) -> Result<GroupRole> {
+        self.shift_rank(group_id, user_id, true).await
+    }
+
+    /// Moves the user down to the previous role and returns it, failing with
+    /// [`RobloxError::NoAdjacentRole`] when they already hold the lowest role.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn demote(&self, group_id: i64, user_id: i64) -> Result<GroupRole> {
+        self.shift_rank(group_id, user_id, false).await
+    }
+
+    /// Requires a cookie with permission to view the group's audit log.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_audit_log(&self, group_id: i64) -> Result<Vec<AuditEntry>> {
+        let url = format!(
+            "{}/v1/groups/{}/audit-log?limit=100&sortOrder=Desc",
+            self.base_url(Api::Groups),
+            group_id
+        );
+
+        self.paginate(Some(Api::Groups), url).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_join_requests(&self, group_id: i64) -> Result<Vec<JoinRequest>> {
+        let url = format!(
+            "{}/v1/groups/{}/join-requests?limit=100",
+            self.base_url(Api::Groups),
+            group_id
+        );
+
+        self.paginate(Some(Api::Groups), url).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn accept_join_request(&self, group_id: i64, user_id: i64) -> Result<()> {
+        let url = self.join_request_url(group_id, user_id);
+        self.send_empty(Some(Api::Groups), Method::POST, &url, None)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn decline_join_request(&self, group_id: i64, user_id: i64) -> Result<()> {
+        let url = self.join_request_url(group_id, user_id);
+        self.send_empty(Some(Api::Groups), Method::DELETE, &url, None)
+            .await
+    }
+
+    /// Joins the group as the authenticated user, or asks to when the group
+    /// doesn't allow public entry. Checks membership afterwards to tell which
+    /// happened, so this costs three requests.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn join_group(&self, group_id: i64) -> Result<JoinStatus> {
+        let url = format!(
+            "{}/v1/groups/{}/users",
+            self.base_url(Api::Groups),
+            group_id
+        );
+        self.send_empty(Some(Api::Groups), Method::POST, &url, Some(&json!({})))
+            .await?;
+        let user_id = self.get_authenticated_user().await?;
+
+        Ok(
+            match self.get_user_rank_in_group(user_id, group_id).await? {
+                Some(_) => JoinStatus::Joined,
+                None => JoinStatus::Pending,
+            },
+        )
+    }
+
+    /// Removes the authenticated user from the group.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn leave_group(&self, group_id: i64) -> Result<()> {
+        let user_id = self.get_authenticated_user().await?;
+        let url = format!(
+            "{}/v1/groups/{}/users/{}",
+            self.base_url(Api::Groups),
+            group_id,
+            user_id
+        );
+        self.send_empty(Some(Api::Groups), Method::DELETE, &url, None)
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_authenticated_user(&self) -> Result<i64> {
+        let url = format!("{}/v1/users/authenticated", self.base_url(Api::Users));
+        let body = self.get_value(Some(Api::Users), &url).await?;
+
+        body["id"].as_i64().ok_or_else(|| missing_field(&url, "id"))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_universe(&self, universe_id: i64) -> Result<UniverseInfo> {
+        let url = format!(
+            "{}/v1/games?universeIds={}",
+            self.base_url(Api::Games),
+            universe_id
+        );
+        let mut body = self.get_value(Some(Api::Games), &url).await?;
+        let mut universes: Vec<UniverseInfo> = parse(&url, body["data"].take())?;
+        if universes.is_empty() {
+            return Err(RobloxError::NotFound);
+        }
+
+        Ok(universes.swap_remove(0))
+    }
+
+    /// Lists the group's public games. The v2 endpoint leaves out the live
+    /// player counts and the creator's name.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_games(&self, group_id: i64) -> Result<Vec<UniverseInfo>> {
+        let url = format!(
+            "{}/v2/groups/{}/games?accessFilter=Public&limit=50",
+            self.base_url(Api::Games),
+            group_id
+        );
+
+        self.cursor_pages::<GameV2>(Some(Api::Games), url)
+            .map_ok(UniverseInfo::from)
+            .try_collect()
+            .await
+    }
+
+    /// Lists the user's favorited games, failing with
+    /// [`RobloxError::Forbidden`] when their favorites are private. Like
+    /// [`Client::get_group_games`], player counts and creator names are left
+    /// out.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_favorite_games(&self, user_id: i64) -> Result<Vec<UniverseInfo>> {
+        let url = format!(
+            "{}/v2/users/{}/favorite/games?limit=50",
+            self.base_url(Api::Games),
+            user_id
+        );
+
+        self.cursor_pages::<GameV2>(Some(Api::Games), url)
+            .map_ok(UniverseInfo::from)
+            .try_collect()
+            .await
+    }
+
+    /// Lists the games the user played recently. Roblox only shows this to
+    /// the user themselves, so anyone else fails with
+    /// [`RobloxError::Forbidden`].
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_recent_games(&self, user_id: i64) -> Result<Vec<UniverseInfo>> {
+        let url = format!(
+            "{}/v2/users/{}/games/recently-played?limit=25",
+            self.base_url(Api::Games),
+            user_id
+        );
+
+        self.cursor_pages::<GameV2>(Some(Api::Games), url)
+            .map_ok(UniverseInfo::from)
+            .try_collect()
+            .await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_servers(
+        &self,
+        place_id: i64,
+        server_type: ServerType,
+    ) -> Result<Vec<GameServer>> {
+        let url = format!(
+            "{}/v1/games/{}/servers/{}?limit=100",
+            self.base_url(Api::Games),
+            place_id,
+            server_type.as_str()
+        );
+
+        self.paginate(Some(Api::Games), url).await
+    }
+
+    /// Asks to join a specific server of the place, as the launcher does.
+    /// Needs a cookie, and Roblox's bot protection often refuses requests that
+    /// don't look like they came from a real client, which shows up as
+    /// [`RobloxError::Forbidden`]. Treat this as best-effort.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_server_join_script(&self, place_id: i64, game_id: &str) -> Result<JoinScript> {
+        let url = format!("{}/v1/join-game-instance", self.base_url(Api::GameJoin));
+        let request = json!({ "placeId": place_id, "gameId": game_id, "isTeleport": false });
+        let body = self.post_value(Some(Api::GameJoin), &url, &request).await?;
+        let status = body["status"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "status"))?;
+        let connection = &body["joinScript"]["ServerConnections"][0];
+
+        Ok(JoinScript {
+            status,
+            job_id: body["jobId"].as_str().map(str::to_string),
+            join_script_url: body["joinScriptUrl"].as_str().map(str::to_string),
+            server_address: connection["Address"].as_str().map(str::to_string),
+            server_port: connection["Port"].as_i64(),
+            message: body["message"].as_str().map(str::to_string),
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_universe_gamepasses(&self, universe_id: i64) -> Result<Vec<GamePass>> {
+        let url = format!(
+            "{}/v1/games/{}/game-passes?limit=100",
+            self.base_url(Api::Games),
+            universe_id
+        );
+
+        self.paginate(Some(Api::Games), url).await
+    }
+
+    /// Requires a cookie for an account that can manage the universe.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_developer_products(&self, universe_id: i64) -> Result<Vec<DeveloperProduct>> {
+        let mut products = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/developer-products/v1/developer-products/list?universeId={}&page={}",
+                self.base_url(Api::OpenCloud),
+                universe_id,
+                page
+            );
+            let mut body = self.get_value(Some(Api::OpenCloud), &url).await?;
+            if body["DeveloperProducts"].is_array() {
+                products.extend(parse::<Vec<DeveloperProduct>>(
+                    &url,
+                    body["DeveloperProducts"].take(),
+                )?);
+            }
+            if body["FinalPage"].as_bool() != Some(false) {
+                break;
+            }
+        }
+        Ok(products)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_game_votes(&self, universe_id: i64) -> Result<Votes> {
+        let url = format!(
+            "{}/v1/games/votes?universeIds={}",
+            self.base_url(Api::Games),
+            universe_id
+        );
+        let mut body = self.get_value(Some(Api::Games), &url).await?;
+        let votes: Vec<Votes> = parse(&url, body["data"].take())?;
+
+        votes.into_iter().next().ok_or(RobloxError::NotFound)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn place_id_to_universe_id(&self, place_id: i64) -> Result<i64> {
+        let url = format!(
+            "{}/universes/v1/places/{}/universe",
+            self.base_url(Api::OpenCloud),
+            place_id
+        );
+        let body = self.get_value(Some(Api::OpenCloud), &url).await?;
+
+        body["universeId"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "universeId"))
+    }
+
+    /// Roblox has no batch endpoint for this, so it's one request per place,
+    /// run with the client's concurrency. Places that don't exist or have no
+    /// universe are left out of the map; any other failure is returned.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn place_ids_to_universe_ids(&self, place_ids: &[i64]) -> Result<HashMap<i64, i64>> {
+        let universe_ids = self
+            .for_each_id(place_ids, |place_id| async move {
+                match self.place_id_to_universe_id(place_id).await {
+                    Ok(universe_id) => Ok(Some(universe_id)),
+                    Err(RobloxError::NotFound) | Err(RobloxError::MissingField { .. }) => Ok(None),
+                    Err(err) => Err(err),
+                }
+            })
+            .await?;
+
+        Ok(universe_ids
+            .into_iter()
+            .filter_map(|(place_id, universe_id)| Some((place_id, universe_id?)))
+            .collect())
+    }
+
+    /// Requires a cookie with permission to view the group's funds.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_group_funds(&self, group_id: i64) -> Result<i64> {
+        let url = format!(
+            "{}/v1/groups/{}/currency",
+            self.base_url(Api::Economy),
+            group_id
+        );
+        let body = self.get_value(Some(Api::Economy), &url).await?;
 
-#[derive(Debug)]
-pub enum RobloxError {
-    Reqwest(ReqwestError),
-    MissingField,
-}
+        body["robux"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "robux"))
+    }
 
-type Result<T> = StdResult<T, RobloxError>;
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_authenticated_balance(&self) -> Result<i64> {
+        let url = format!("{}/v1/user/currency", self.base_url(Api::Economy));
+        let body = self.get_value(Some(Api::Economy), &url).await?;
 
-#[derive(Clone, Default)]
-pub struct Client {
-    client: ReqwestClient,
-}
+        body["robux"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "robux"))
+    }
 
-impl Client {
-    pub async fn get_user_roles(&self, roblox_id: i64) -> Result<HashMap<i64, i64>> {
+    /// Pays `amount` Robux out of the group's funds to a member. Requires a
+    /// cookie with permission to spend group funds, and fails with
+    /// [`RobloxError::InsufficientFunds`] when the group can't cover it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn pay_group_member(&self, group_id: i64, user_id: i64, amount: i64) -> Result<()> {
         let url = format!(
-            "https://groups.roblox.com/v2/users/{}/groups/roles",
-            roblox_id
+            "{}/v1/groups/{}/payouts",
+            self.base_url(Api::Economy),
+            group_id
         );
-        let body: Value = self.client.get(&url).send().await?.json::<Value>().await?;
+        let request = json!({
+            "PayoutType": "FixedAmount",
+            "Recipients": [payout_recipient(user_id, amount)],
+        });
 
-        if let Some(resp) = body["data"].as_array() {
-            let mut ranks = HashMap::new();
-            for rank in resp.iter() {
-                ranks.insert(
-                    rank["group"]["id"].as_i64().unwrap(),
-                    rank["role"]["rank"].as_i64().unwrap(),
-                );
+        self.send_payout(Some(Api::Economy), &url, &request).await
+    }
+
+    /// Replaces the group's recurring payouts with `percentages`, given as
+    /// `(user_id, percent)` pairs. An empty slice stops all recurring payouts.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_recurring_payouts(
+        &self,
+        group_id: i64,
+        percentages: &[(i64, i64)],
+    ) -> Result<()> {
+        let url = format!(
+            "{}/v1/groups/{}/payouts/recurring",
+            self.base_url(Api::Economy),
+            group_id
+        );
+        let recipients = percentages
+            .iter()
+            .map(|&(user_id, percent)| payout_recipient(user_id, percent))
+            .collect::<Vec<_>>();
+        let request = json!({ "PayoutType": "Percentage", "Recipients": recipients });
+
+        self.send_payout(Some(Api::Economy), &url, &request).await
+    }
+
+    /// Lists the authenticated user's trades in the given state.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_trades(&self, status: TradeStatus) -> Result<Vec<TradeSummary>> {
+        let url = format!(
+            "{}/v1/trades/{}?limit=100",
+            self.base_url(Api::Trades),
+            status.as_str()
+        );
+
+        self.paginate(Some(Api::Trades), url).await
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_trade(&self, trade_id: i64) -> Result<TradeDetail> {
+        let url = format!("{}/v1/trades/{}", self.base_url(Api::Trades), trade_id);
+        let body = self.get_value(Some(Api::Trades), &url).await?;
+
+        parse(&url, body)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn accept_trade(&self, trade_id: i64) -> Result<()> {
+        let url = self.trade_action_url(trade_id, "accept");
+        self.send_trade(Some(Api::Trades), &url, &json!({})).await?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn decline_trade(&self, trade_id: i64) -> Result<()> {
+        let url = self.trade_action_url(trade_id, "decline");
+        self.send_trade(Some(Api::Trades), &url, &json!({})).await?;
+
+        Ok(())
+    }
+
+    /// Answers a trade with a different offer, returning the new trade's ID.
+    /// Looks up both the trade and the authenticated user first, so this
+    /// costs three requests.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn counter_trade(&self, trade_id: i64, offer: TradeOfferInput) -> Result<i64> {
+        let partner_id = self.get_trade(trade_id).await?.user.id;
+        let user_id = self.get_authenticated_user().await?;
+        let request = json!({
+            "offers": [
+                {
+                    "userId": partner_id,
+                    "userAssetIds": offer.requested_user_asset_ids,
+                    "robux": offer.requested_robux,
+                },
+                {
+                    "userId": user_id,
+                    "userAssetIds": offer.offered_user_asset_ids,
+                    "robux": offer.offered_robux,
+                },
+            ],
+        });
+        let url = self.trade_action_url(trade_id, "counter");
+        let body = json_body(self.send_trade(Some(Api::Trades), &url, &request).await?)?;
+
+        body["id"].as_i64().ok_or_else(|| missing_field(&url, "id"))
+    }
+
+    /// Reads a standard DataStore entry through Open Cloud. Requires an API
+    /// key with DataStore read access for the universe.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_datastore_entry(
+        &self,
+        universe_id: i64,
+        datastore: &str,
+        key: &str,
+    ) -> Result<Value> {
+        let url = format!(
+            "{}/datastores/v1/universes/{}/standard-datastores/datastore/entry?datastoreName={}&entryKey={}",
+            self.base_url(Api::OpenCloud),
+            universe_id,
+            encode(datastore),
+            encode(key)
+        );
+
+        self.get_value(Some(Api::OpenCloud), &url)
+            .await
+            .map_err(open_cloud_error)
+    }
+
+    /// Publishes `message` to `topic` on every live server of the universe
+    /// through Open Cloud MessagingService.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn publish_message(
+        &self,
+        universe_id: i64,
+        topic: &str,
+        message: &str,
+    ) -> Result<()> {
+        let url = format!(
+            "{}/messaging-service/v1/universes/{}/topics/{}",
+            self.base_url(Api::OpenCloud),
+            universe_id,
+            encode(topic)
+        );
+        self.send_empty(
+            Some(Api::OpenCloud),
+            Method::POST,
+            &url,
+            Some(&json!({ "message": message })),
+        )
+        .await
+        .map_err(open_cloud_error)
+    }
+
+    /// Fetches `url` with the client's authentication, retries and rate
+    /// limiting, and deserializes the response into `T`. Meant for endpoints
+    /// this crate doesn't wrap yet; building a correct URL is up to the
+    /// caller, usually starting from [`Client::base_url`]. The cookie and API
+    /// key are only sent when `url` is on one of the client's base URLs.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let body = self.get_value(self.api_for(url), url).await?;
+
+        parse(url, body)
+    }
+
+    /// Like [`Client::get_json`], but POSTs `body` as JSON with CSRF handling.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn post_json<T: DeserializeOwned>(&self, url: &str, body: &Value) -> Result<T> {
+        let body = self.post_value(self.api_for(url), url, body).await?;
+
+        parse(url, body)
+    }
+
+    /// Payouts answer a 400 for everything from a bad recipient to an empty
+    /// group wallet, so the body is checked for the latter before giving up.
+    async fn send_payout(&self, api: Option<Api>, url: &str, request: &Value) -> Result<()> {
+        match self.send_empty(api, Method::POST, url, Some(request)).await {
+            Err(RobloxError::HttpStatus { code: 400, body })
+                if body.to_lowercase().contains("insufficient") =>
+            {
+                Err(RobloxError::InsufficientFunds)
             }
-            return Ok(ranks);
+            result => result,
         }
+    }
 
-        Err(RobloxError::MissingField)
+    async fn set_blocked(&self, user_id: i64, action: &str) -> Result<()> {
+        let url = format!(
+            "{}/v1/users/{}/{}",
+            self.base_url(Api::AccountSettings),
+            user_id,
+            action
+        );
+        self.send_rejectable(Some(Api::AccountSettings), Method::POST, &url, None)
+            .await
     }
 
-    pub async fn get_username_from_id(&self, roblox_id: i64) -> Result<String> {
-        let url = format!("https://api.roblox.com/users/{}", roblox_id);
-        let body = self.client.get(&url).send().await?.json::<Value>().await?;
+    async fn friend_request_action(&self, user_id: i64, action: &str) -> Result<()> {
+        let url = format!(
+            "{}/v1/users/{}/{}",
+            self.base_url(Api::Friends),
+            user_id,
+            action
+        );
+        match self
+            .send_empty(Some(Api::Friends), Method::POST, &url, Some(&json!({})))
+            .await
+        {
+            // 5 is the other user's cap, 31 the authenticated user's own.
+            Err(RobloxError::HttpStatus { code: 400, body }) => Err(match error_detail(&body) {
+                (Some(5), _) | (Some(31), _) => RobloxError::TooManyFriends,
+                (_, message) => RobloxError::Rejected(message),
+            }),
+            result => result,
+        }
+    }
 
-        body["Username"]
-            .as_str()
-            .map_or(Err(RobloxError::MissingField), |r| Ok(r.to_string()))
+    /// Roblox answers actions on a finished trade with a 400 and error code 2.
+    async fn send_trade(&self, api: Option<Api>, url: &str, body: &Value) -> Result<String> {
+        self.post_with_csrf(api, url, body)
+            .await
+            .map_err(|err| match err {
+                RobloxError::HttpStatus { code: 400, body } => match error_detail(&body) {
+                    (Some(2), _) => RobloxError::TradeInactive,
+                    (_, message) => RobloxError::Rejected(message),
+                },
+                err => err,
+            })
     }
 
-    pub async fn get_id_from_username(&self, username: &str) -> Result<Option<i64>> {
+    /// The raw `{ group, role }` list behind every per-user group lookup, all
+    /// read from the one endpoint so deduplication can share the request.
+    /// Returns the URL alongside it for error reporting.
+    async fn get_memberships(&self, user_id: i64) -> Result<(String, Vec<Value>)> {
+        let url = format!(
+            "{}/v1/users/{}/groups/roles",
+            self.base_url(Api::Groups),
+            user_id
+        );
+        let mut body = self.get_value(Some(Api::Groups), &url).await?;
+        match body["data"].take() {
+            Value::Array(memberships) => Ok((url, memberships)),
+            _ => Err(missing_field(&url, "data")),
+        }
+    }
+
+    async fn get_friends_count(&self, user_id: i64, kind: &str) -> Result<i64> {
         let url = format!(
-            "https://api.roblox.com/users/get-by-username?username={}",
-            username
+            "{}/v1/users/{}/{}/count",
+            self.base_url(Api::Friends),
+            user_id,
+            kind
         );
-        let body = self.client.get(&url).send().await?.json::<Value>().await?;
+        let body = self.get_value(Some(Api::Friends), &url).await?;
 
-        Ok(body["Id"].as_i64())
+        body["count"]
+            .as_i64()
+            .ok_or_else(|| missing_field(&url, "count"))
     }
 
-    pub async fn has_asset(&self, roblox_id: i64, item: i64, asset_type: &str) -> Result<bool> {
+    async fn owns_item(&self, roblox_id: i64, item: i64, asset_type: &str) -> Result<bool> {
         let url = format!(
-            "https://inventory.roblox.com/v1/users/{}/items/{}/{}",
-            roblox_id, asset_type, item
+            "{}/v1/users/{}/items/{}/{}",
+            self.base_url(Api::Inventory),
+            roblox_id,
+            asset_type,
+            item
         );
-        let body = self.client.get(&url).send().await?.json::<Value>().await?;
+        let body = self.get_value(Some(Api::Inventory), &url).await?;
         if let Some(data) = body["data"].as_array() {
             return Ok(!data.is_empty());
         }
         Ok(false)
     }
 
-    pub async fn check_code(&self, roblox_id: i64, code: &str) -> Result<bool> {
-        let url = format!("https://www.roblox.com/users/{}/profile", roblox_id);
-        let body = self.client.get(&url).send().await?.text().await?;
+    async fn get_thumbnails(
+        &self,
+        path: &str,
+        ids_param: &str,
+        ids: &[i64],
+        size: ThumbnailSize,
+    ) -> Result<HashMap<i64, Option<String>>> {
+        let ids = ids.iter().map(i64::to_string).collect::<Vec<_>>().join(",");
+        let url = format!(
+            "{}{}?{}={}&size={}&format=Png",
+            self.base_url(Api::Thumbnails),
+            path,
+            ids_param,
+            ids,
+            size
+        );
+        let body = self.get_value(Some(Api::Thumbnails), &url).await?;
+
+        let mut thumbnails = HashMap::new();
+        if let Some(data) = body["data"].as_array() {
+            for thumbnail in data.iter() {
+                let target_id = thumbnail["targetId"]
+                    .as_i64()
+                    .ok_or_else(|| missing_field(&url, "targetId"))?;
+                thumbnails.insert(target_id, completed_image_url(thumbnail));
+            }
+        }
+        Ok(thumbnails)
+    }
+
+    /// Like [`Client::paginator`], for endpoints numbered by `page` and
+    /// `itemsPerPage` rather than cursors.
+    fn numbered_pages<T>(&self, api: Option<Api>, url: String, per_page: usize) -> Paginator<'_, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        Paginator::new(self, api, url, PageToken::Page { page: 1, per_page })
+    }
+
+    fn cursor_pages<T>(&self, api: Option<Api>, url: String) -> Paginator<'_, T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        Paginator::new(self, api, url, PageToken::Cursor(String::new()))
+    }
 
-        Ok(body.contains(code))
+    async fn paginate<T>(&self, api: Option<Api>, url: String) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        self.cursor_pages(api, url).try_collect().await
     }
 
-    pub async fn get_group_rank(&self, group_id: i64, rank_id: i64) -> Result<Option<Value>> {
-        let url = format!("https://groups.roblox.com/v1/groups/{}/roles", group_id);
-        let body = self.client.get(&url).send().await?.json::<Value>().await?;
-        let ranks_array = match body["roles"].as_array() {
-            Some(a) => a,
-            None => return Ok(None),
+    async fn get_page<T: DeserializeOwned>(
+        &self,
+        api: Option<Api>,
+        url: &str,
+        token: PageToken,
+    ) -> Result<(Vec<T>, Option<PageToken>)> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let url = match &token {
+            PageToken::Cursor(cursor) if cursor.is_empty() => url.to_string(),
+            PageToken::Cursor(cursor) => format!("{}{}cursor={}", url, separator, encode(cursor)),
+            PageToken::Page { page, per_page } => format!(
+                "{}{}page={}&itemsPerPage={}",
+                url, separator, page, per_page
+            ),
+        };
+        let page: Page<T> = self.get_parsed(api, &url).await?;
+        let items = page.data.unwrap_or_default();
+        let next = match token {
+            PageToken::Cursor(_) => page.next_page_cursor.map(PageToken::Cursor),
+            // Numbered pages just run out; a short one is the last.
+            PageToken::Page { page, per_page } if items.len() >= per_page => {
+                Some(PageToken::Page {
+                    page: page + 1,
+                    per_page,
+                })
+            }
+            PageToken::Page { .. } => None,
         };
-        let rank = match ranks_array
+
+        Ok((items, next))
+    }
+
+    async fn for_each_id<T, F, Fut>(&self, ids: &[i64], f: F) -> Result<HashMap<i64, T>>
+    where
+        F: Fn(i64) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        stream::iter(ids.iter().copied())
+            .map(|id| {
+                let fut = f(id);
+                async move { Ok::<_, RobloxError>((id, fut.await?)) }
+            })
+            .buffer_unordered(self.concurrency)
+            .try_collect()
+            .await
+    }
+
+    fn trade_action_url(&self, trade_id: i64, action: &str) -> String {
+        format!(
+            "{}/v1/trades/{}/{}",
+            self.base_url(Api::Trades),
+            trade_id,
+            action
+        )
+    }
+
+    fn join_request_url(&self, group_id: i64, user_id: i64) -> String {
+        format!(
+            "{}/v1/groups/{}/join-requests/users/{}",
+            self.base_url(Api::Groups),
+            group_id,
+            user_id
+        )
+    }
+
+    /// Moves by position in the rank-sorted roles rather than by rank number,
+    /// since ranks are rarely contiguous. The guest role (rank 0) isn't one a
+    /// member can be moved into, so it's left out.
+    async fn shift_rank(&self, group_id: i64, user_id: i64, up: bool) -> Result<GroupRole> {
+        let rank = self
+            .get_user_rank_in_group(user_id, group_id)
+            .await?
+            .filter(|&rank| rank > 0)
+            .ok_or(RobloxError::NotInGroup)?;
+        let mut roles = self.get_roles(group_id).await?;
+        roles.retain(|r| r.rank > 0);
+        roles.sort_by_key(|r| r.rank);
+
+        let index = roles
             .iter()
-            .find(|r| r["rank"].as_i64().unwrap_or_default() == rank_id)
-        {
-            Some(r) => r,
-            None => return Ok(None),
+            .position(|r| r.rank == rank)
+            .ok_or_else(|| missing_field(&self.roles_url(group_id), "rank"))?;
+        let target = if up {
+            roles.get(index + 1)
+        } else {
+            index.checked_sub(1).and_then(|i| roles.get(i))
+        }
+        .ok_or(RobloxError::NoAdjacentRole)?
+        .clone();
+
+        self.set_rank(group_id, user_id, target.id).await?;
+        Ok(target)
+    }
+
+    fn roles_url(&self, group_id: i64) -> String {
+        format!(
+            "{}/v1/groups/{}/roles",
+            self.base_url(Api::Groups),
+            group_id
+        )
+    }
+
+    async fn get_roles(&self, group_id: i64) -> Result<Vec<GroupRole>> {
+        if let Some(roles) = self.cached_roles(group_id) {
+            return Ok(roles);
+        }
+
+        let url = self.roles_url(group_id);
+        let list: RoleList = self.get_parsed(Some(Api::Groups), &url).await?;
+        let roles = list.roles.unwrap_or_default();
+
+        if self.role_cache_ttl.is_some() {
+            let mut cache = self.role_cache.write().unwrap();
+            cache.insert(group_id, (Instant::now(), roles.clone()));
+        }
+        Ok(roles)
+    }
+
+    fn cached_roles(&self, group_id: i64) -> Option<Vec<GroupRole>> {
+        let ttl = self.role_cache_ttl?;
+        let cache = self.role_cache.read().unwrap();
+        match cache.get(&group_id) {
+            Some((fetched, roles)) if fetched.elapsed() < ttl => Some(roles.clone()),
+            _ => None,
+        }
+    }
+
+    /// Waits on the overall limiter, then on the bucket of the API family
+    /// the request is for.
+    async fn acquire_permit(&self, api: Option<Api>) {
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.acquire().await;
+        }
+        if let Some(limiter) = api.and_then(|api| self.endpoint_limiters.get(&api)) {
+            limiter.acquire().await;
+        }
+    }
+
+    /// Takes one of the [`ClientBuilder::max_concurrent`] slots. Callers hold
+    /// it until the body has been read, since the connection is busy until then.
+    async fn concurrency_permit(&self) -> Option<SemaphorePermit<'_>> {
+        match &self.in_flight_limit {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .expect("the request semaphore is never closed"),
+            ),
+            None => None,
+        }
+    }
+
+    /// Sends `request`, reporting it and any response to the observer.
+    async fn send(&self, url: &str, request: RequestBuilder) -> StdResult<Response, ReqwestError> {
+        let observer = match &self.observer {
+            Some(observer) => observer,
+            None => return request.send().await,
         };
-        Ok(Some(rank.to_owned()))
+        observer.on_request(url);
+        let started = Instant::now();
+        let result = request.send().await;
+        if let Ok(resp) = &result {
+            observer.on_response(url, resp.status().as_u16(), started.elapsed());
+        }
+
+        result
     }
 
-    pub async fn get_group_ranks(
+    fn observe_error(&self, url: &str, err: RobloxError) -> RobloxError {
+        if let Some(observer) = &self.observer {
+            observer.on_error(url, &err);
+        }
+        err
+    }
+
+    /// Picks the next client in round-robin order when there are proxies.
+    fn http_client(&self) -> &ReqwestClient {
+        if self.clients.len() == 1 {
+            return &self.clients[0];
+        }
+        let next = self.next_client.fetch_add(1, Ordering::Relaxed);
+
+        &self.clients[next % self.clients.len()]
+    }
+
+    /// `api` is the family `url` was built for, or `None` for a caller's URL
+    /// that isn't on any of the client's hosts, which gets no credentials.
+    fn request(&self, api: Option<Api>, method: Method, url: &str) -> RequestBuilder {
+        debug!(%method, url, "sending roblox request");
+        let mut builder = self
+            .http_client()
+            .request(method, url)
+            .header(USER_AGENT, &self.user_agent);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        // The API key is only meant for Open Cloud, so other hosts never see it.
+        if let (Some(api_key), Some(Api::OpenCloud)) = (&self.api_key, api) {
+            builder = builder.header(API_KEY, api_key);
+        }
+        match (&self.cookie, api) {
+            (Some(cookie), Some(_)) => builder.header(COOKIE, format!(".ROBLOSECURITY={}", cookie)),
+            _ => builder,
+        }
+    }
+
+    /// GETs `url` and reads the body, holding the concurrency slot throughout.
+    async fn get(&self, api: Option<Api>, url: &str) -> Result<String> {
+        let mut attempt = 0;
+        let mut rate_limited = false;
+        let mut rotated = false;
+        loop {
+            self.acquire_permit(api).await;
+            let permit = self.concurrency_permit().await;
+            let result = self.send(url, self.request(api, Method::GET, url)).await;
+            if let Err(err) = &result {
+                if is_transient(err) && self.clients.len() > 1 && !rotated {
+                    debug!(url, "proxy unreachable, retrying through the next one");
+                    rotated = true;
+                    continue;
+                }
+            }
+            if let Ok(resp) = &result {
+                if resp.status() == StatusCode::TOO_MANY_REQUESTS && !rate_limited {
+                    if let Some(max) = self.max_rate_limit_delay {
+                        rate_limited = true;
+                        let delay = retry_after(resp).unwrap_or(DEFAULT_RATE_LIMIT_DELAY);
+                        debug!(url, ?delay, "rate limited, retrying");
+                        drop(permit);
+                        sleep(delay.min(max)).await;
+                        continue;
+                    }
+                }
+            }
+            let retryable = match &result {
+                Ok(resp) => resp.status().is_server_error(),
+                Err(err) => is_transient(err),
+            };
+            match self.retry {
+                Some(policy) if retryable && attempt + 1 < policy.max_attempts => {
+                    let delay = policy.backoff(attempt);
+                    debug!(url, attempt, ?delay, "request failed, retrying");
+                    drop(permit);
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => {
+                    let resp = result.map_err(|err| self.observe_error(url, err.into()))?;
+                    let body = check_status(resp).await?.text().await?;
+                    drop(permit);
+                    return Ok(body);
+                }
+            }
+        }
+    }
+
+    /// Sends a mutating request, fetching or refreshing the cached
+    /// `X-CSRF-TOKEN` whenever Roblox rejects it with a 403. Returns the body.
+    async fn send_with_csrf(
         &self,
-        group_id: i64,
-        min_rank: i64,
-        max_rank: i64,
-    ) -> Result<Vec<Value>> {
-        let url = format!("https://groups.roblox.com/v1/groups/{}/roles", group_id);
-        let body = self.client.get(&url).send().await?.json::<Value>().await?;
-        let ranks_array = match body["roles"].as_array() {
-            Some(a) => a,
-            None => return Ok(Vec::new()),
+        api: Option<Api>,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> Result<String> {
+        let mut refreshed = false;
+        let mut rotated = false;
+        loop {
+            let mut request = self.request(api, method.clone(), url);
+            if let (Some(token), Some(_)) = (self.csrf_token.read().unwrap().as_deref(), api) {
+                request = request.header(CSRF_TOKEN, token);
+            }
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+            self.acquire_permit(api).await;
+            let permit = self.concurrency_permit().await;
+            let resp = match self.send(url, request).await {
+                Err(err) if is_transient(&err) && self.clients.len() > 1 && !rotated => {
+                    debug!(url, "proxy unreachable, retrying through the next one");
+                    rotated = true;
+                    continue;
+                }
+                resp => resp.map_err(|err| self.observe_error(url, err.into()))?,
+            };
+            if resp.status() == StatusCode::FORBIDDEN && api.is_some() {
+                if let Some(token) = resp.headers().get(CSRF_TOKEN) {
+                    // A token that is rejected straight after being refreshed
+                    // means the session itself is not valid.
+                    if refreshed {
+                        return Err(RobloxError::Unauthorized);
+                    }
+                    if let Ok(token) = token.to_str() {
+                        debug!(url, "refreshing csrf token");
+                        *self.csrf_token.write().unwrap() = Some(token.to_string());
+                        refreshed = true;
+                        continue;
+                    }
+                }
+            }
+            let body = check_status(resp).await?.text().await?;
+            drop(permit);
+            return Ok(body);
+        }
+    }
+
+    /// Sends a mutation whose response body, often empty even on a 200, is
+    /// never parsed. Only the status decides whether it succeeded.
+    async fn send_empty(
+        &self,
+        api: Option<Api>,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> Result<()> {
+        self.send_with_csrf(api, method, url, body).await?;
+
+        Ok(())
+    }
+
+    /// Like [`Client::send_empty`], for mutations where a 400 carries a reason
+    /// worth handing back as [`RobloxError::Rejected`].
+    async fn send_rejectable(
+        &self,
+        api: Option<Api>,
+        method: Method,
+        url: &str,
+        body: Option<&Value>,
+    ) -> Result<()> {
+        match self.send_empty(api, method, url, body).await {
+            Err(RobloxError::HttpStatus { code: 400, body }) => {
+                Err(RobloxError::Rejected(error_detail(&body).1))
+            }
+            result => result,
+        }
+    }
+
+    async fn post_with_csrf(&self, api: Option<Api>, url: &str, body: &Value) -> Result<String> {
+        self.send_with_csrf(api, Method::POST, url, Some(body))
+            .await
+    }
+
+    async fn get_value(&self, api: Option<Api>, url: &str) -> Result<Value> {
+        json_body(self.get_body(api, url).await?)
+    }
+
+    /// Like [`Client::get_value`], but parses the body straight into `T`.
+    async fn get_parsed<T: DeserializeOwned>(&self, api: Option<Api>, url: &str) -> Result<T> {
+        parse_body(url, self.get_body(api, url).await?)
+    }
+
+    /// The raw body of a GET, shared with any identical request already in
+    /// flight when deduplication is on.
+    async fn get_body(&self, api: Option<Api>, url: &str) -> Result<String> {
+        let in_flight = match &self.in_flight {
+            Some(in_flight) => in_flight,
+            None => return self.get(api, url).await,
         };
-        let ranks = ranks_array
-            .iter()
-            .filter_map(|r| {
-                let rank = r["rank"].as_i64().unwrap();
-                if rank >= min_rank && rank <= max_rank {
-                    return Some(r.to_owned());
+        let shared = {
+            let mut in_flight = in_flight.lock().unwrap();
+            match in_flight.get(url) {
+                Some(shared) => {
+                    debug!(url, "joining in-flight request");
+                    shared.clone()
                 }
-                None
-            })
-            .collect::<Vec<Value>>();
+                None => {
+                    let client = self.clone();
+                    let key = url.to_string();
+                    let request = async move {
+                        let result = client.get(api, &key).await;
+                        if let Some(in_flight) = &client.in_flight {
+                            in_flight.lock().unwrap().remove(&key);
+                        }
+                        result.map_err(Arc::new)
+                    };
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let shared = request.boxed().shared();
+                    #[cfg(target_arch = "wasm32")]
+                    let shared = request.boxed_local().shared();
+                    in_flight.insert(url.to_string(), shared.clone());
+                    shared
+                }
+            }
+        };
 
-        Ok(ranks)
+        shared
+            .await
+            .map_err(|err| Arc::try_unwrap(err).unwrap_or_else(|err| RobloxError::duplicate(&err)))
+    }
+
+    async fn post_value(&self, api: Option<Api>, url: &str, body: &Value) -> Result<Value> {
+        json_body(self.post_with_csrf(api, url, body).await?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+type PageStream<'a, T> = BoxStream<'a, Result<T>>;
+#[cfg(target_arch = "wasm32")]
+type PageStream<'a, T> = LocalBoxStream<'a, Result<T>>;
+
+/// One page of a listing, as sent.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    data: Option<Vec<T>>,
+    next_page_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RoleList {
+    roles: Option<Vec<GroupRole>>,
+}
+
+/// Where the next page of a listing starts. Most endpoints hand out a
+/// `nextPageCursor`; a few older ones (like outfits) take a page number.
+enum PageToken {
+    Cursor(String),
+    Page { page: u32, per_page: usize },
+}
+
+/// A paginated listing, fetched one page at a time as the stream is polled.
+/// A failed page fetch is yielded as an error and ends the stream.
+pub struct Paginator<'a, T> {
+    items: PageStream<'a, T>,
+}
+
+impl<'a, T> Paginator<'a, T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    fn new(client: &'a Client, api: Option<Api>, url: String, first: PageToken) -> Self {
+        // Remembering cursors guards against Roblox handing back one it has
+        // already sent, which would otherwise loop forever.
+        let state = (Some(first), HashSet::new());
+        let pages = stream::try_unfold(state, move |(token, mut seen)| {
+            let url = url.clone();
+            async move {
+                let token = match token {
+                    Some(token) => token,
+                    None => return Ok(None),
+                };
+                let (items, next) = client.get_page::<T>(api, &url, token).await?;
+                let next = next.filter(|next| match next {
+                    PageToken::Cursor(cursor) => seen.insert(cursor.clone()),
+                    PageToken::Page { .. } => true,
+                });
+                Ok::<_, RobloxError>(Some((items, (next, seen))))
+            }
+        })
+        .map_ok(|items| stream::iter(items.into_iter().map(Ok)))
+        .try_flatten();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let items = pages.boxed();
+        #[cfg(target_arch = "wasm32")]
+        let items = pages.boxed_local();
+        Paginator { items }
+    }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.items.poll_next_unpin(cx)
+    }
+}
+
+/// Letters and digits that are hard to mix up when read back: no `0`/`O`/`o`,
+/// `1`/`I`/`l`.
+pub const VERIFICATION_CODE_CHARSET: &str =
+    "ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnpqrstuvwxyz23456789";
+
+/// Generates a random code of `length` characters drawn from `charset`, for a
+/// user to paste into their profile description before calling
+/// [`Client::check_code`]. Pass [`VERIFICATION_CODE_CHARSET`] unless the UI
+/// needs something narrower.
+///
+/// Panics if `length` is zero or `charset` is empty, since an empty code would
+/// match every description.
+pub fn generate_verification_code(length: usize, charset: &str) -> String {
+    let chars = charset.chars().collect::<Vec<_>>();
+    assert!(length > 0, "verification codes need at least one character");
+    assert!(!chars.is_empty(), "verification code charset is empty");
+
+    (0..length)
+        .map(|_| chars[rand::random_range(0..chars.len())])
+        .collect()
+}
+
+/// Decodes a response body as JSON, keeping the start of the body around when
+/// Roblox hands back something else (such as a Cloudflare error page).
+fn json_body(body: String) -> Result<Value> {
+    serde_json::from_str(&body)
+        .map_err(|_| RobloxError::Parse(body.chars().take(PARSE_SNIPPET_LEN).collect()))
+}
+
+/// Parses a JSON object or array body straight into `T`, with simd-json when
+/// the `simd-json` feature is on. Error pages (such as Cloudflare's) aren't
+/// JSON at all, so those are caught first and reported by their start.
+fn parse_body<T: DeserializeOwned>(url: &str, body: String) -> Result<T> {
+    if !matches!(body.trim_start().chars().next(), Some('{') | Some('[')) {
+        return Err(RobloxError::Parse(
+            body.chars().take(PARSE_SNIPPET_LEN).collect(),
+        ));
+    }
+    decode(body).map_err(|err| RobloxError::Parse(format!("{} in response from {}", err, url)))
+}
+
+#[cfg(not(feature = "simd-json"))]
+fn decode<T: DeserializeOwned>(body: String) -> StdResult<T, String> {
+    serde_json::from_str(&body).map_err(|err| err.to_string())
+}
+
+/// simd-json parses in place, which is why the body is taken by value.
+#[cfg(feature = "simd-json")]
+fn decode<T: DeserializeOwned>(body: String) -> StdResult<T, String> {
+    let mut body = body.into_bytes();
+    simd_json::serde::from_slice(&mut body).map_err(|err| err.to_string())
+}
+
+fn parse<T: DeserializeOwned>(url: &str, value: Value) -> Result<T> {
+    serde_json::from_value(value)
+        .map_err(|err| RobloxError::Parse(format!("{} in response from {}", err, url)))
+}
+
+fn completed_image_url(thumbnail: &Value) -> Option<String> {
+    match thumbnail["state"].as_str() {
+        Some("Completed") => thumbnail["imageUrl"].as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+fn payout_recipient(user_id: i64, amount: i64) -> Value {
+    json!({ "recipientId": user_id, "recipientType": "User", "amount": amount })
+}
+
+/// The first code and message out of Roblox's `{"errors": [...]}` envelope,
+/// falling back to the whole body as the message when it isn't one.
+fn error_detail(body: &str) -> (Option<i64>, String) {
+    let error = serde_json::from_str::<Value>(body)
+        .map(|mut body| body["errors"][0].take())
+        .unwrap_or(Value::Null);
+    let message = match error["message"].as_str() {
+        Some(message) => message.to_string(),
+        None => body.to_string(),
+    };
+
+    (error["code"].as_i64(), message)
+}
+
+fn missing_field(url: &str, field: &'static str) -> RobloxError {
+    RobloxError::MissingField {
+        url: url.to_string(),
+        field,
+    }
+}
+
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, NON_ALPHANUMERIC).to_string()
+}
+
+async fn check_status(resp: Response) -> Result<Response> {
+    debug!(url = %resp.url(), status = resp.status().as_u16(), "received roblox response");
+    match resp.status() {
+        StatusCode::UNAUTHORIZED => Err(RobloxError::Unauthorized),
+        StatusCode::FORBIDDEN => Err(RobloxError::Forbidden),
+        StatusCode::NOT_FOUND => Err(RobloxError::NotFound),
+        StatusCode::TOO_MANY_REQUESTS => Err(RobloxError::RateLimited {
+            retry_after: retry_after(&resp),
+        }),
+        status if !status.is_success() => Err(RobloxError::HttpStatus {
+            code: status.as_u16(),
+            body: resp.text().await?,
+        }),
+        _ => Ok(resp),
+    }
+}
+
+/// Open Cloud rejects API keys that lack the scope for a resource with a 403,
+/// which is an authentication problem rather than a per-user permission one.
+fn open_cloud_error(err: RobloxError) -> RobloxError {
+    match err {
+        RobloxError::Forbidden => RobloxError::Unauthorized,
+        err => err,
+    }
+}
+
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_transient(err: &ReqwestError) -> bool {
+    err.is_connect() || err.is_timeout()
+}
+
+// The fetch backend can't tell a failed connection apart from other request
+// errors.
+#[cfg(target_arch = "wasm32")]
+fn is_transient(err: &ReqwestError) -> bool {
+    err.is_request() || err.is_timeout()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
+
+impl fmt::Display for RobloxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RobloxError::Reqwest(err) => err.fmt(f),
+            RobloxError::MissingField { url, field } => {
+                write!(f, "missing field '{}' in response from {}", field, url)
+            }
+            RobloxError::Parse(body) => write!(f, "could not parse roblox response: {}", body),
+            RobloxError::HttpStatus { code, body } => {
+                write!(f, "roblox responded with status {}: {}", code, body)
+            }
+            RobloxError::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "rate limited, retry after {:?}", retry_after),
+            RobloxError::RateLimited { retry_after: None } => f.write_str("rate limited"),
+            RobloxError::Unauthorized => f.write_str("missing or invalid authentication"),
+            RobloxError::Forbidden => f.write_str("missing permission for this action"),
+            RobloxError::NotFound => f.write_str("not found"),
+            RobloxError::Timeout => f.write_str("request to roblox timed out"),
+            RobloxError::NotInGroup => f.write_str("user is not in the group"),
+            RobloxError::NoAdjacentRole => {
+                f.write_str("user is already at the highest or lowest role")
+            }
+            RobloxError::InsufficientFunds => f.write_str("the group doesn't have enough robux"),
+            RobloxError::Rejected(message) => write!(f, "roblox rejected the request: {}", message),
+            RobloxError::TooManyFriends => f.write_str("user has reached the friend limit"),
+            RobloxError::TradeInactive => f.write_str("the trade is no longer active"),
+            RobloxError::Shared(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for RobloxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RobloxError::Reqwest(err) => Some(err),
+            RobloxError::Shared(err) => err.source(),
+            _ => None,
+        }
     }
 }
 
 impl From<ReqwestError> for RobloxError {
     fn from(err: ReqwestError) -> Self {
+        if err.is_timeout() {
+            return RobloxError::Timeout;
+        }
         RobloxError::Reqwest(err)
     }
 }
@@ -0,0 +1,454 @@
+use super::*;
+use std::collections::VecDeque;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// A canned response for [`MockServer`] to hand out.
+#[derive(Clone)]
+struct Reply {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+    delay: Duration,
+}
+
+impl Reply {
+    fn json(status: u16, body: Value) -> Self {
+        Reply {
+            status,
+            headers: vec![("Content-Type", "application/json".to_string())],
+            body: body.to_string(),
+            delay: Duration::from_secs(0),
+        }
+    }
+
+    fn empty(status: u16) -> Self {
+        Reply {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+            delay: Duration::from_secs(0),
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// A bare HTTP/1.1 server on localhost that answers with the queued replies
+/// in order, repeating the last one once the queue runs dry.
+struct MockServer {
+    url: String,
+    hits: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    async fn start(replies: Vec<Reply>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let mut replies: VecDeque<_> = replies.into();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let reply = match replies.len() {
+                    1 => replies[0].clone(),
+                    _ => replies.pop_front().expect("no replies queued"),
+                };
+                tokio::spawn(serve(stream, reply));
+            }
+        });
+
+        MockServer { url, hits }
+    }
+
+    /// How many connections the server has answered, one per request.
+    fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+}
+
+async fn serve(mut stream: TcpStream, reply: Reply) {
+    let mut request = Vec::new();
+    let mut buf = [0; 4096];
+    let head_end = loop {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+        if let Some(at) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break at + 4;
+        }
+    };
+    let head = String::from_utf8_lossy(&request[..head_end]).to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+    while request.len() < head_end + content_length {
+        match stream.read(&mut buf).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+    tokio::time::sleep(reply.delay).await;
+    let mut response = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        reply.status,
+        reply.body.len()
+    );
+    for (name, value) in &reply.headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(&reply.body);
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn client(server: &MockServer, api: Api) -> Client {
+    Client::builder()
+        .base_url(api, server.url.as_str())
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn get_user_roles_reports_a_null_group_id() {
+    let server = MockServer::start(vec![Reply::json(
+        200,
+        json!({ "data": [{ "group": { "id": null }, "role": { "rank": 1 } }] }),
+    )])
+    .await;
+    let client = client(&server, Api::Groups);
+
+    match client.get_user_roles(1).await {
+        Err(RobloxError::MissingField { field, .. }) => assert_eq!(field, "group.id"),
+        other => panic!("expected MissingField, got {:?}", other),
+    }
+}
+
+fn user() -> Value {
+    json!({ "id": 1, "name": "builderman", "displayName": "builderman" })
+}
+
+#[tokio::test]
+async fn retries_until_a_request_succeeds() {
+    let server = MockServer::start(vec![
+        Reply::json(500, json!({})),
+        Reply::json(503, json!({})),
+        Reply::json(200, user()),
+    ])
+    .await;
+    let client = Client::builder()
+        .base_url(Api::Users, server.url.as_str())
+        .with_retries(3, Duration::from_millis(1))
+        .build()
+        .unwrap();
+
+    assert_eq!(client.get_user_info(1).await.unwrap().id, 1);
+    assert_eq!(server.hits(), 3);
+}
+
+#[tokio::test]
+async fn waits_out_a_rate_limit_then_retries() {
+    let server = MockServer::start(vec![
+        Reply::json(429, json!({})).header("Retry-After", "1"),
+        Reply::json(200, user()),
+    ])
+    .await;
+    let client = client(&server, Api::Users);
+
+    let started = std::time::Instant::now();
+    assert_eq!(client.get_user_info(1).await.unwrap().id, 1);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test]
+async fn returns_rate_limits_when_retrying_is_disabled() {
+    let server =
+        MockServer::start(vec![Reply::json(429, json!({})).header("Retry-After", "1")]).await;
+    let client = Client::builder()
+        .base_url(Api::Users, server.url.as_str())
+        .disable_rate_limit_retry()
+        .build()
+        .unwrap();
+
+    match client.get_user_info(1).await {
+        Err(RobloxError::RateLimited { retry_after }) => {
+            assert_eq!(retry_after, Some(Duration::from_secs(1)))
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+    assert_eq!(server.hits(), 1);
+}
+
+#[tokio::test]
+async fn times_out_a_slow_response() {
+    let server = MockServer::start(vec![
+        Reply::json(200, user()).delayed(Duration::from_secs(2))
+    ])
+    .await;
+    let client = Client::builder()
+        .base_url(Api::Users, server.url.as_str())
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+
+    match client.get_user_info(1).await {
+        Err(RobloxError::Timeout) => {}
+        other => panic!("expected Timeout, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn has_premium_reads_a_bare_true() {
+    let server = MockServer::start(vec![Reply::json(200, json!(true))]).await;
+    let client = client(&server, Api::PremiumFeatures);
+
+    assert!(client.has_premium(1).await.unwrap());
+}
+
+#[tokio::test]
+async fn has_premium_reads_a_bare_false() {
+    let server = MockServer::start(vec![Reply::json(200, json!(false))]).await;
+    let client = client(&server, Api::PremiumFeatures);
+
+    assert!(!client.has_premium(1).await.unwrap());
+}
+
+#[tokio::test]
+async fn has_premium_needs_authentication() {
+    let server = MockServer::start(vec![Reply::json(401, json!({}))]).await;
+    let client = client(&server, Api::PremiumFeatures);
+
+    match client.has_premium(1).await {
+        Err(RobloxError::Unauthorized) => {}
+        other => panic!("expected Unauthorized, got {:?}", other),
+    }
+}
+
+fn user_described(description: &str) -> Value {
+    let mut user = user();
+    user["description"] = json!(description);
+    user
+}
+
+#[tokio::test]
+async fn check_code_finds_the_code_in_the_description() {
+    let server = MockServer::start(vec![Reply::json(
+        200,
+        user_described("hi! apple banana cherry"),
+    )])
+    .await;
+    let client = client(&server, Api::Users);
+
+    assert!(client
+        .check_code(1, " apple banana cherry\n")
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn check_code_misses_a_description_without_it() {
+    let server = MockServer::start(vec![Reply::json(200, user_described("hi!"))]).await;
+    let client = client(&server, Api::Users);
+
+    assert!(!client.check_code(1, "apple banana cherry").await.unwrap());
+}
+
+#[tokio::test]
+async fn check_code_never_matches_a_blank_code() {
+    let server = MockServer::start(vec![Reply::json(200, user_described("hi!"))]).await;
+    let client = client(&server, Api::Users);
+
+    assert!(!client.check_code(1, "").await.unwrap());
+    assert!(!client.check_code(1, " \n\t").await.unwrap());
+    assert_eq!(server.hits(), 0);
+}
+
+fn memberships(group_id: i64, rank: i64) -> Value {
+    json!({ "data": [{ "group": { "id": group_id }, "role": { "id": 10, "rank": rank } }] })
+}
+
+#[tokio::test]
+async fn is_ranked_at_least_includes_the_minimum_rank() {
+    let server = MockServer::start(vec![Reply::json(200, memberships(7, 100))]).await;
+    let client = client(&server, Api::Groups);
+
+    assert!(client.is_ranked_at_least(1, 7, 100).await.unwrap());
+}
+
+#[tokio::test]
+async fn is_ranked_at_least_rejects_one_rank_below() {
+    let server = MockServer::start(vec![Reply::json(200, memberships(7, 99))]).await;
+    let client = client(&server, Api::Groups);
+
+    assert!(!client.is_ranked_at_least(1, 7, 100).await.unwrap());
+}
+
+#[tokio::test]
+async fn is_ranked_at_least_rejects_non_members() {
+    let server = MockServer::start(vec![Reply::json(200, memberships(8, 255))]).await;
+    let client = client(&server, Api::Groups);
+
+    assert!(!client.is_ranked_at_least(1, 7, 0).await.unwrap());
+}
+
+#[tokio::test]
+async fn accepts_an_empty_success_body() {
+    let server = MockServer::start(vec![Reply::empty(200)]).await;
+    let client = client(&server, Api::Groups);
+
+    client.post_shout(7, "hello").await.unwrap();
+}
+
+fn roles() -> Value {
+    json!({
+        "groupId": 7,
+        "roles": [
+            { "id": 10, "name": "Guest", "rank": 0 },
+            { "id": 13, "name": "Owner", "rank": 255 },
+            { "id": 11, "name": "Member", "rank": 1 },
+            { "id": 12, "name": "Admin", "rank": 50 },
+        ],
+    })
+}
+
+async fn shift(rank: i64, up: bool, replies: Vec<Reply>) -> (Result<GroupRole>, usize) {
+    let mut queue = vec![
+        Reply::json(200, memberships(7, rank)),
+        Reply::json(200, roles()),
+    ];
+    queue.extend(replies);
+    let server = MockServer::start(queue).await;
+    let client = client(&server, Api::Groups);
+    let result = if up {
+        client.promote(7, 1).await
+    } else {
+        client.demote(7, 1).await
+    };
+
+    (result, server.hits())
+}
+
+#[tokio::test]
+async fn promote_fails_at_the_top_role() {
+    match shift(255, true, Vec::new()).await {
+        (Err(RobloxError::NoAdjacentRole), 2) => {}
+        other => panic!("expected NoAdjacentRole, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn demote_fails_at_the_bottom_role() {
+    match shift(1, false, Vec::new()).await {
+        (Err(RobloxError::NoAdjacentRole), 2) => {}
+        other => panic!("expected NoAdjacentRole, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn shift_rank_steps_over_gaps_in_ranks() {
+    let (promoted, hits) = shift(1, true, vec![Reply::empty(200)]).await;
+    assert_eq!(promoted.unwrap().rank, 50);
+    assert_eq!(hits, 3);
+
+    let (demoted, hits) = shift(255, false, vec![Reply::empty(200)]).await;
+    assert_eq!(demoted.unwrap().rank, 50);
+    assert_eq!(hits, 3);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn days_between_rounds_down_to_whole_days() {
+    let created = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+    let day = chrono::Duration::days(1);
+
+    assert_eq!(days_between(created, created + day * 99 / 100), 0);
+    assert_eq!(days_between(created, created + day), 1);
+    assert_eq!(days_between(created, created + day * 101 / 100), 1);
+}
+
+#[test]
+fn generates_codes_from_the_charset() {
+    let code = generate_verification_code(12, "ab");
+
+    assert_eq!(code.len(), 12);
+    assert!(code.chars().all(|c| c == 'a' || c == 'b'));
+}
+
+#[test]
+#[should_panic]
+fn refuses_an_empty_charset() {
+    generate_verification_code(12, "");
+}
+
+#[test]
+#[should_panic]
+fn refuses_a_zero_length_code() {
+    generate_verification_code(0, VERIFICATION_CODE_CHARSET);
+}
+
+#[tokio::test]
+async fn wall_posts_unwrap_the_poster_member() {
+    let member = json!({
+        "user": { "userId": 1, "username": "builderman", "displayName": "builderman" },
+        "role": { "id": 11, "name": "Member", "rank": 1 },
+    });
+    let server = MockServer::start(vec![Reply::json(
+        200,
+        json!({
+            "nextPageCursor": null,
+            "data": [
+                { "id": 1, "poster": member, "body": "hi", "created": "2020-01-01T00:00:00Z" },
+                { "id": 2, "poster": null, "body": "bye", "created": "2020-01-01T00:00:00Z" },
+            ],
+        }),
+    )])
+    .await;
+    let client = client(&server, Api::Groups);
+
+    let posts = client.get_wall_posts(7).await.unwrap();
+    assert_eq!(posts[0].poster.as_ref().unwrap().user_id, 1);
+    assert!(posts[1].poster.is_none());
+}
+
+#[tokio::test]
+async fn reports_the_start_of_a_page_that_is_not_json() {
+    let mut reply = Reply::empty(200);
+    reply.body = "<html>Just a moment...</html>".to_string();
+    let server = MockServer::start(vec![reply]).await;
+    let client = client(&server, Api::Groups);
+
+    match client.get_role_members(7, 11).await {
+        Err(RobloxError::Parse(snippet)) => assert!(snippet.starts_with("<html>")),
+        other => panic!("expected Parse, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn poll_for_code_checks_at_least_once() {
+    let server = MockServer::start(vec![Reply::json(200, user_described("apple"))]).await;
+    let client = client(&server, Api::Users);
+
+    let found = client
+        .poll_for_code(1, "apple", Duration::from_millis(1), 0)
+        .await
+        .unwrap();
+    assert!(found);
+    assert_eq!(server.hits(), 1);
+}